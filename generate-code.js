@@ -70,30 +70,30 @@ use serde::{Deserialize, Serialize};
 
   // Generate shorthand constants
   rustCode += `// Shorthand field mappings\n`;
-  rustCode += `pub const PHONE_SHORTHAND_KEYS: &[&str] = &[\n`;
-  for (const shorthand of schema.shorthandFields.phoneShorthands) {
-    const allKeys = [shorthand.key, ...shorthand.aliases];
-    for (const key of allKeys) {
-      rustCode += `    "${key}",\n`;
+  const shorthandGroups = [
+    ['PHONE', schema.shorthandFields.phoneShorthands],
+    ['EMAIL', schema.shorthandFields.emailShorthands],
+    ['ADDRESS', schema.shorthandFields.addressShorthands],
+  ];
+  for (const [prefix, shorthands] of shorthandGroups) {
+    rustCode += `pub const ${prefix}_SHORTHAND_KEYS: &[&str] = &[\n`;
+    for (const shorthand of shorthands) {
+      for (const key of [shorthand.key, ...shorthand.aliases]) {
+        rustCode += `    "${key}",\n`;
+      }
     }
+    rustCode += `];\n\n`;
   }
-  rustCode += `];\n\n`;
 
-  // Generate EMAIL_SHORTHAND_KEYS - use single line for short arrays
-  const emailShorthandKeys = [];
-  for (const shorthand of schema.shorthandFields.emailShorthands) {
-    const allKeys = [shorthand.key, ...shorthand.aliases];
-    emailShorthandKeys.push(...allKeys);
-  }
-  
-  if (emailShorthandKeys.length <= 3) {
-    rustCode += `pub const EMAIL_SHORTHAND_KEYS: &[&str] = &[${emailShorthandKeys.map(k => `"${k}"`).join(', ')}];\n`;
-  } else {
-    rustCode += `pub const EMAIL_SHORTHAND_KEYS: &[&str] = &[\n`;
-    for (const key of emailShorthandKeys) {
-      rustCode += `    "${key}",\n`;
+  // (key, canonical type) pairs for expanding shorthands into typed entries
+  for (const [prefix, shorthands] of shorthandGroups) {
+    rustCode += `pub const ${prefix}_SHORTHANDS: &[(&str, &str)] = &[\n`;
+    for (const shorthand of shorthands) {
+      for (const key of [shorthand.key, ...shorthand.aliases]) {
+        rustCode += `    ("${key}", "${shorthand.defaultType}"),\n`;
+      }
     }
-    rustCode += `];\n`;
+    rustCode += `];\n\n`;
   }
 
   // Write to file
@@ -201,10 +201,24 @@ const EMAIL_SHORTHAND_KEYS = [
 
   grammarCode += `];
 
+// Address shorthand keys
+const ADDRESS_SHORTHAND_KEYS = [
+`;
+
+  for (const shorthand of schema.shorthandFields.addressShorthands) {
+    const allKeys = [shorthand.key, ...shorthand.aliases];
+    for (const key of allKeys) {
+      grammarCode += `  '${key}',\n`;
+    }
+  }
+
+  grammarCode += `];
+
 // Export for use in grammar.js
 module.exports = {
   PHONE_SHORTHAND_KEYS,
-  EMAIL_SHORTHAND_KEYS
+  EMAIL_SHORTHAND_KEYS,
+  ADDRESS_SHORTHAND_KEYS
 };
 `;
 
@@ -262,11 +276,22 @@ export const YCARD_COMPLETION_ITEMS: CompletionItem[] = [
     label: '${shorthand.key}',
     kind: CompletionItemKind.Property,
     data: ${dataIndex++},
-    detail: 'Email address (shorthand)',
+    detail: '${shorthand.defaultType === 'other' ? 'Email address' : shorthand.defaultType.charAt(0).toUpperCase() + shorthand.defaultType.slice(1) + ' email'} (shorthand)',
     insertText: '${shorthand.key}: "example@domain.com"'
   },\n`;
   }
 
+  // Address shorthands
+  for (const shorthand of schema.shorthandFields.addressShorthands) {
+    completionCode += `  {
+    label: '${shorthand.key}',
+    kind: CompletionItemKind.Property,
+    data: ${dataIndex++},
+    detail: '${shorthand.defaultType.charAt(0).toUpperCase() + shorthand.defaultType.slice(1)} address (shorthand)',
+    insertText: '${shorthand.key}: "123 Main St, Anytown"'
+  },\n`;
+  }
+
   completionCode += `];
 `;
 
@@ -313,6 +338,74 @@ pub const DIAGNOSTIC_CODES: &[DiagnosticCode] = &[
   console.log('    ✅ ycard-core/src/generated_diagnostics.rs');
 }
 
+// Generate structural schema definitions
+function generateSchemaDefinitions() {
+  console.log('  📝 Generating schema field definitions...');
+
+  let rustCode = `// =============================================================================
+// GENERATED FILE - DO NOT EDIT MANUALLY
+// =============================================================================
+// This file is automatically generated from schema.json
+// To make changes, edit schema.json and run: node generate-code.js
+// Generated on: ${new Date().toISOString()}
+// =============================================================================
+
+pub struct FieldDef {
+    pub name: &'static str,
+    /// Scalar (\`string\`, \`number\`, \`boolean\`), type or enum name, \`[]\` suffix for arrays
+    pub ty: &'static str,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+pub struct TypeDef {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub fields: &'static [FieldDef],
+}
+
+`;
+
+  const fieldList = (fields, indent) => {
+    let out = '';
+    for (const field of fields) {
+      out += `${indent}FieldDef {
+${indent}    name: "${field.name}",
+${indent}    ty: "${field.type}",
+${indent}    required: ${field.required},
+${indent}    description: ${JSON.stringify(field.description)},
+${indent}},\n`;
+    }
+    return out;
+  };
+
+  rustCode += `pub const ROOT_FIELDS: &[FieldDef] = &[\n`;
+  rustCode += fieldList(schema.schema.fields, '    ');
+  rustCode += `];\n\n`;
+
+  rustCode += `pub const TYPE_DEFS: &[TypeDef] = &[\n`;
+  for (const [typeName, typeDef] of Object.entries(schema.schema.types)) {
+    rustCode += `    TypeDef {
+        name: "${typeName}",
+        description: ${JSON.stringify(typeDef.description)},
+        fields: &[
+${fieldList(typeDef.fields, '            ')}        ],
+    },\n`;
+  }
+  rustCode += `];\n\n`;
+
+  rustCode += `/// Canonical variants of each enum
+pub const ENUM_VARIANTS: &[(&str, &[&str])] = &[\n`;
+  for (const [enumName, enumDef] of Object.entries(schema.enums)) {
+    const variants = enumDef.variants.map(v => `"${v.canonical}"`).join(', ');
+    rustCode += `    ("${enumName}", &[${variants}]),\n`;
+  }
+  rustCode += `];\n`;
+
+  safeWriteFile('ycard-core/src/generated_schema.rs', rustCode);
+  console.log('    ✅ ycard-core/src/generated_schema.rs');
+}
+
 // Generate i18n alias mappings
 function generateI18nAliases() {
   console.log('  🌍 Generating i18n alias mappings...');
@@ -324,6 +417,7 @@ function generateI18nAliases() {
     "__GENERATED_ON__": new Date().toISOString(),
     "__WARNING__": "Edit schema.json instead of this file",
     version: new Date().toISOString().slice(0, 10).replace(/-/g, '.'),
+    schemaVersion: schema.version,
     locales: {}
   };
   
@@ -341,6 +435,9 @@ function generateI18nAliases() {
     if (config.honorifics) {
       aliasPack.locales[locale].honorifics = config.honorifics;
     }
+    if (config.messages) {
+      aliasPack.locales[locale].messages = config.messages;
+    }
   });
   
   const aliasesJson = JSON.stringify(aliasPack, null, 2);
@@ -356,6 +453,7 @@ function main() {
     generateGrammarRules();
     generateLSPCompletions();
     generateDiagnosticCodes();
+    generateSchemaDefinitions();
     generateI18nAliases();
     
     console.log('\n✅ Code generation complete!');
@@ -374,4 +472,4 @@ if (require.main === module) {
   main();
 }
 
-module.exports = { generateRustTypes, generateTypeScriptTypes, generateGrammarRules, generateLSPCompletions, generateDiagnosticCodes, generateI18nAliases };
\ No newline at end of file
+module.exports = { generateRustTypes, generateTypeScriptTypes, generateGrammarRules, generateLSPCompletions, generateDiagnosticCodes, generateSchemaDefinitions, generateI18nAliases };
\ No newline at end of file
@@ -1,9 +1,9 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use tracing::{error, info};
-use ycard_core::{self as ycard, PhonesStyle, ValidationMode};
+use ycard_core::{self as ycard, PhoneDisplayStyle, PhonesStyle, ValidationMode};
 
 #[derive(Parser)]
 #[command(name = "ycard")]
@@ -44,6 +44,10 @@ enum Commands {
         /// Use strict parsing mode (no field normalization)
         #[arg(long)]
         strict: bool,
+
+        /// Mark a preferred phone and email when none is: none, first, or a type order (mobile,home)
+        #[arg(long)]
+        prefer: Option<String>,
     },
 
     /// Format yCard file
@@ -62,6 +66,14 @@ enum Commands {
         /// Relocalize keys to specified locale
         #[arg(long)]
         relocalize_keys: Option<String>,
+
+        /// Render phones for display (auto, national, international) instead of E.164
+        #[arg(long)]
+        phone_display: Option<String>,
+
+        /// Mark a preferred phone and email when none is: none, first, or a type order (mobile,home)
+        #[arg(long)]
+        prefer: Option<String>,
     },
 
     /// Check/validate yCard file
@@ -72,6 +84,72 @@ enum Commands {
         /// Use strict validation mode
         #[arg(long)]
         strict: bool,
+
+        /// Check canonical yCard JSON structurally against the schema
+        #[arg(long, conflicts_with = "strict")]
+        schema_only: bool,
+
+        /// Configure a rule or diagnostic code: CODE=off|on|error|warning|info|hint
+        #[arg(long = "rule", value_name = "CODE=SETTING")]
+        rules: Vec<String>,
+    },
+
+    /// Parse several yCard files and list them in name order
+    Sort {
+        /// Input file paths
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Collation locale, such as de-u-co-phonebk or es-u-co-trad (defaults to --locale)
+        #[arg(long)]
+        collation: Option<String>,
+
+        /// Output the sorted cards as a JSON array instead of names and paths
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the JSON Schema (Draft 2020-12) of canonical yCard JSON
+    Schema {
+        /// Write the schema to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Inspect alias packs
+    Aliases {
+        #[command(subcommand)]
+        command: AliasesCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum AliasesCommand {
+    /// Report conflicting and ambiguous aliases in the loaded packs
+    Lint {
+        /// Alias pack files to layer on top of the bundled pack and --alias-pack
+        packs: Vec<PathBuf>,
+
+        /// Fail on warnings as well as errors
+        #[arg(long)]
+        deny_warnings: bool,
+
+        /// Hide findings below this level: error, warning, info, hint
+        #[arg(long, default_value = "hint")]
+        min_level: String,
+    },
+
+    /// List loaded alias packs, lowest priority first
+    List,
+
+    /// Show which pack and locale resolve a word
+    Explain {
+        /// Key, type or country name to look up
+        word: String,
+
+        /// Alias table to search: key, type or country
+        #[arg(long, default_value = "key")]
+        kind: String,
     },
 }
 
@@ -98,18 +176,18 @@ async fn main() -> Result<()> {
 
     if let Some(alias_pack_path) = &cli.alias_pack {
         info!("Loading alias pack from: {}", alias_pack_path.display());
-        let pack_content = fs::read_to_string(alias_pack_path)
+        load_pack_file(&mut alias_manager, alias_pack_path)
             .await
-            .context("Failed to read alias pack file")?;
-
-        alias_manager
-            .load_pack(&pack_content)
             .context("Failed to load alias pack")?;
     }
 
     // Set default locale
     let locale = if cli.locale == "auto" {
-        // Would detect from system locale in real implementation
+        // Each document is detected on its own; the environment is the fallback
+        if let Some(guess) = ycard::locale_from_env() {
+            info!("Fallback locale from environment: {}", guess.locale);
+            alias_manager.set_default_locale(&guess.locale);
+        }
         None
     } else {
         alias_manager.set_default_locale(&cli.locale);
@@ -121,26 +199,97 @@ async fn main() -> Result<()> {
             file,
             json_ast,
             strict,
-        } => parse_command(file, json_ast, strict, locale, alias_manager).await,
+            prefer,
+        } => {
+            let parser = ycard::Parser::with_alias_manager(alias_manager)
+                .with_preference(preference_inference(prefer.as_deref())?);
+            parse_command(file, json_ast, strict, locale, parser).await
+        }
         Commands::Fmt {
             file,
             write,
             phones_style,
             relocalize_keys,
+            phone_display,
+            prefer,
         } => {
             fmt_command(
                 file,
                 write,
                 phones_style,
                 relocalize_keys,
+                phone_display,
                 locale,
-                alias_manager,
+                ycard::Parser::with_alias_manager(alias_manager)
+                    .with_preference(preference_inference(prefer.as_deref())?),
             )
             .await
         }
-        Commands::Check { file, strict } => {
-            check_command(file, strict, locale, alias_manager).await
+        Commands::Check {
+            file,
+            strict,
+            schema_only,
+            rules,
+        } => check_command(file, strict, schema_only, rules, locale, alias_manager).await,
+        Commands::Sort {
+            files,
+            collation,
+            json,
+        } => {
+            sort_command(
+                files,
+                collation,
+                json,
+                locale,
+                ycard::Parser::with_alias_manager(alias_manager),
+            )
+            .await
         }
+        Commands::Schema { output } => schema_command(output).await,
+        Commands::Aliases { command } => match command {
+            AliasesCommand::Lint {
+                packs,
+                deny_warnings,
+                min_level,
+            } => aliases_lint_command(packs, deny_warnings, min_level, alias_manager).await,
+            AliasesCommand::List => {
+                for pack in alias_manager.packs() {
+                    println!(
+                        "{} {} priority={} locales={}",
+                        pack.name,
+                        pack.version,
+                        pack.priority,
+                        pack.locales.join(",")
+                    );
+                }
+                Ok(())
+            }
+            AliasesCommand::Explain { word, kind } => {
+                aliases_explain_command(&word, &kind, locale, &alias_manager)
+            }
+        },
+    }
+}
+
+fn preference_inference(prefer: Option<&str>) -> Result<ycard::PreferenceInference> {
+    match prefer {
+        None => Ok(ycard::PreferenceInference::None),
+        Some(s) => ycard::PreferenceInference::from_str_opt(s)
+            .ok_or_else(|| anyhow!("Invalid prefer setting: {:?}", s)),
+    }
+}
+
+/// The locale `--locale auto` resolves to for this document
+fn document_locale(parser: &ycard::Parser, content: &str) -> String {
+    match parser.detect_locale(content) {
+        Ok(Some(guess)) => {
+            info!(
+                "Detected locale {} (confidence {:.2})",
+                guess.locale, guess.confidence
+            );
+            guess.locale
+        }
+        _ => parser.alias_manager().default_locale().to_string(),
     }
 }
 
@@ -149,21 +298,24 @@ async fn parse_command(
     json_ast: bool,
     strict: bool,
     locale: Option<&str>,
-    alias_manager: ycard::AliasManager,
+    parser: ycard::Parser,
 ) -> Result<()> {
     let content = fs::read_to_string(&file)
         .await
         .context("Failed to read input file")?;
 
-    let parser = ycard::Parser::with_alias_manager(alias_manager);
-
     let ycard = if strict {
         parser.parse_strict(&content)
     } else {
-        // Use lenient parsing by default for human-friendly behavior
-        parser.parse_lenient(&content, locale)
+        // Use lenient parsing by default for human-friendly behavior,
+        // resolving aliases in the document's own locale unless one is given
+        let locale = match locale {
+            Some(locale) => locale.to_string(),
+            None => document_locale(&parser, &content),
+        };
+        parser.parse_lenient(&content, Some(&locale))
     }
-    .context("Failed to parse yCard")?;
+    .map_err(|e| parse_failure(&file, &e))?;
 
     if json_ast {
         let json = serde_json::to_string_pretty(&ycard).context("Failed to serialize to JSON")?;
@@ -181,17 +333,17 @@ async fn fmt_command(
     write: bool,
     phones_style: String,
     relocalize_keys: Option<String>,
+    phone_display: Option<String>,
     locale: Option<&str>,
-    alias_manager: ycard::AliasManager,
+    parser: ycard::Parser,
 ) -> Result<()> {
     let content = fs::read_to_string(&file)
         .await
         .context("Failed to read input file")?;
 
-    let parser = ycard::Parser::with_alias_manager(alias_manager);
     let ycard = parser
         .parse_lenient(&content, locale)
-        .context("Failed to parse yCard")?;
+        .map_err(|e| parse_failure(&file, &e))?;
 
     let phones_style = match phones_style.as_str() {
         "canonical" => PhonesStyle::Canonical,
@@ -203,10 +355,27 @@ async fn fmt_command(
         }
     };
 
-    let formatter = ycard::Formatter::new()
+    let mut formatter = ycard::Formatter::new()
         .with_phones_style(phones_style)
         .with_relocalize_keys(relocalize_keys);
 
+    if let Some(phone_display) = phone_display {
+        let style = PhoneDisplayStyle::from_str_opt(&phone_display).unwrap_or_else(|| {
+            error!("Invalid phone-display: {}. Using auto.", phone_display);
+            PhoneDisplayStyle::Auto
+        });
+        let locale = match locale {
+            Some(locale) => locale.to_string(),
+            None => document_locale(&parser, &content),
+        };
+        formatter = formatter.with_phone_display(style, &locale);
+    }
+
+    // Show the form of the name meant for readers of an explicit --locale
+    if let Some(locale) = locale {
+        formatter = formatter.with_name_locale(locale);
+    }
+
     let formatted = formatter.format(&ycard).context("Failed to format yCard")?;
 
     if write {
@@ -221,9 +390,49 @@ async fn fmt_command(
     Ok(())
 }
 
+async fn sort_command(
+    files: Vec<PathBuf>,
+    collation: Option<String>,
+    json: bool,
+    locale: Option<&str>,
+    parser: ycard::Parser,
+) -> Result<()> {
+    let mut cards = Vec::with_capacity(files.len());
+    for file in files {
+        let content = fs::read_to_string(&file)
+            .await
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let ycard = parser
+            .parse_lenient(&content, locale)
+            .map_err(|e| parse_failure(&file, &e))?;
+        cards.push((file, ycard));
+    }
+
+    let collation = collation
+        .or_else(|| locale.map(str::to_string))
+        .unwrap_or_else(|| parser.alias_manager().default_locale().to_string());
+    let collator = ycard::Collator::new(&collation);
+    collator.sort_by_card(&mut cards, |(_, ycard)| ycard);
+
+    if json {
+        let cards: Vec<&ycard::YCard> = cards.iter().map(|(_, ycard)| ycard).collect();
+        let json = serde_json::to_string_pretty(&cards).context("Failed to serialize to JSON")?;
+        println!("{}", json);
+    } else {
+        for (file, ycard) in &cards {
+            let name = ycard.sort_name(&collation).unwrap_or_default();
+            println!("{}\t{}", name, file.display());
+        }
+    }
+
+    Ok(())
+}
+
 async fn check_command(
     file: PathBuf,
     strict: bool,
+    schema_only: bool,
+    rules: Vec<String>,
     locale: Option<&str>,
     alias_manager: ycard::AliasManager,
 ) -> Result<()> {
@@ -231,18 +440,50 @@ async fn check_command(
         .await
         .context("Failed to read input file")?;
 
-    let parser = ycard::Parser::with_alias_manager(alias_manager);
-    let ycard = parser
-        .parse_lenient(&content, locale)
-        .context("Failed to parse yCard")?;
-
-    let mode = if strict {
+    let mode = if schema_only {
+        ValidationMode::SchemaOnly
+    } else if strict {
         ValidationMode::Strict
     } else {
         ValidationMode::Lenient
     };
 
-    let diagnostics = ycard::validate(&ycard, mode).context("Failed to validate yCard")?;
+    let mut validator = ycard::Validator::new(mode);
+    for rule in &rules {
+        let (code, setting) = rule
+            .split_once('=')
+            .with_context(|| format!("Invalid rule setting {:?}, expected CODE=SETTING", rule))?;
+        match setting {
+            "off" => validator.disable_rule(code),
+            "on" => validator.enable_rule(code),
+            level => {
+                let level = ycard::DiagnosticLevel::from_str_opt(level)
+                    .with_context(|| format!("Unknown rule setting: {}", level))?;
+                validator.enable_rule(code);
+                validator.set_severity(code, level);
+            }
+        }
+    }
+
+    let parser = ycard::Parser::with_alias_manager(alias_manager);
+    let mut diagnostics = if schema_only {
+        let value: serde_json::Value =
+            serde_json::from_str(&content).context("Failed to parse yCard JSON")?;
+        validator.validate_value(&value)
+    } else {
+        // Fields that fail to parse are reported with the validation results
+        let (ycard, parse_diagnostics) = parser.parse_partial(&content, locale);
+        validator.validate(&ycard).map(|mut diagnostics| {
+            ycard::locate(&mut diagnostics, &ycard, &content);
+            parse_diagnostics
+                .into_iter()
+                .filter(|d| d.level == ycard::DiagnosticLevel::Error)
+                .chain(diagnostics)
+                .collect::<Vec<_>>()
+        })
+    }
+    .context("Failed to validate yCard")?;
+    ycard::localize(&mut diagnostics, parser.alias_manager(), locale);
 
     if diagnostics.is_empty() {
         println!("✅ {} is valid", file.display());
@@ -262,6 +503,13 @@ async fn check_command(
             if let Some(code) = &diagnostic.code {
                 println!("     Code: {}", code);
             }
+            if let Some(range) = &diagnostic.range {
+                println!(
+                    "     At: line {}, column {}",
+                    range.start.line + 1,
+                    range.start.character + 1
+                );
+            }
         }
 
         let has_errors = diagnostics
@@ -276,10 +524,147 @@ async fn check_command(
     }
 }
 
+/// Load an alias pack file, named after the file unless it names itself
+async fn load_pack_file(alias_manager: &mut ycard::AliasManager, path: &PathBuf) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read alias pack {}", path.display()))?;
+    let mut pack: ycard::AliasPack = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid alias pack {}", path.display()))?;
+    pack.name.get_or_insert_with(|| path.display().to_string());
+    alias_manager
+        .insert_pack(pack)
+        .with_context(|| format!("Failed to load alias pack {}", path.display()))?;
+    Ok(())
+}
+
+fn aliases_explain_command(
+    word: &str,
+    kind: &str,
+    locale: Option<&str>,
+    alias_manager: &ycard::AliasManager,
+) -> Result<()> {
+    let kind = ycard::AliasKind::from_str_opt(kind)
+        .with_context(|| format!("Unknown alias kind: {}", kind))?;
+    match alias_manager.explain(kind, word, locale) {
+        Some(resolution) => {
+            println!(
+                "{} -> {} (pack {}, locale {}; searched {})",
+                word,
+                resolution.target,
+                resolution.pack,
+                resolution.locale,
+                resolution.chain.join(" -> ")
+            );
+            Ok(())
+        }
+        None => {
+            eprintln!("No alias for {:?}", word);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn aliases_lint_command(
+    packs: Vec<PathBuf>,
+    deny_warnings: bool,
+    min_level: String,
+    mut alias_manager: ycard::AliasManager,
+) -> Result<()> {
+    let min_level = ycard::DiagnosticLevel::from_str_opt(&min_level)
+        .with_context(|| format!("Unknown level: {}", min_level))?;
+    for path in &packs {
+        load_pack_file(&mut alias_manager, path).await?;
+    }
+    let names: Vec<String> = alias_manager.packs().into_iter().map(|p| p.name).collect();
+
+    let diagnostics = alias_manager.lint();
+    let count = |level| diagnostics.iter().filter(|d| d.level == level).count();
+    let (errors, warnings) = (
+        count(ycard::DiagnosticLevel::Error),
+        count(ycard::DiagnosticLevel::Warning),
+    );
+
+    let rank = |level: &ycard::DiagnosticLevel| match level {
+        ycard::DiagnosticLevel::Error => (0, "error"),
+        ycard::DiagnosticLevel::Warning => (1, "warning"),
+        ycard::DiagnosticLevel::Info => (2, "info"),
+        ycard::DiagnosticLevel::Hint => (3, "hint"),
+    };
+
+    for diagnostic in diagnostics
+        .iter()
+        .filter(|d| rank(&d.level).0 <= rank(&min_level).0)
+    {
+        let level = rank(&diagnostic.level).1;
+        // Paths start with the pack index; show the pack's name instead
+        let path = diagnostic.path.as_deref().unwrap_or_default();
+        let (index, rest) = path
+            .trim_start_matches('/')
+            .split_once('/')
+            .unwrap_or((path, ""));
+        let pack = index
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| names.get(i))
+            .map_or(index, String::as_str);
+        println!(
+            "{}:/{}: {}[{}]: {}",
+            pack,
+            rest,
+            level,
+            diagnostic.code.as_deref().unwrap_or_default(),
+            diagnostic.message
+        );
+    }
+    println!(
+        "{} errors, {} warnings, {} notes",
+        errors,
+        warnings,
+        diagnostics.len() - errors - warnings
+    );
+
+    if errors > 0 || (deny_warnings && warnings > 0) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// A parse error as `file:line:column: error[code]: message`
+fn parse_failure(file: &Path, error: &ycard::ParseError) -> anyhow::Error {
+    let message = error.to_diagnostic().message;
+    match error.range() {
+        Some(range) => anyhow!(
+            "{}:{}:{}: error[{}]: {}",
+            file.display(),
+            range.start.line + 1,
+            range.start.character + 1,
+            error.code(),
+            message
+        ),
+        None => anyhow!("{}: error[{}]: {}", file.display(), error.code(), message),
+    }
+}
+
+async fn schema_command(output: Option<PathBuf>) -> Result<()> {
+    let schema = serde_json::to_string_pretty(&ycard::json_schema())
+        .context("Failed to serialize JSON Schema")?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, schema + "\n")
+                .await
+                .context("Failed to write JSON Schema")?;
+            info!("Wrote JSON Schema to {}", path.display());
+        }
+        None => println!("{}", schema),
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[tokio::test]
     async fn test_cli_parse() {
         // Would test CLI functionality
@@ -0,0 +1,558 @@
+use crate::registry;
+use crate::schema::AddressComponents;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Structured address component names as they appear in the schema
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddressField {
+    Street,
+    Locality,
+    Region,
+    PostalCode,
+}
+
+impl AddressField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AddressField::Street => "street",
+            AddressField::Locality => "locality",
+            AddressField::Region => "region",
+            AddressField::PostalCode => "postalCode",
+        }
+    }
+
+    fn value<'a>(&self, components: &'a AddressComponents) -> Option<&'a str> {
+        match self {
+            AddressField::Street => components.street.as_deref(),
+            AddressField::Locality => components.locality.as_deref(),
+            AddressField::Region => components.region.as_deref(),
+            AddressField::PostalCode => components.postal_code.as_deref(),
+        }
+        .filter(|v| !v.trim().is_empty())
+    }
+}
+
+/// Postal conventions for a single country
+pub struct CountryRules {
+    /// ISO-3166 alpha-2 code
+    pub country: &'static str,
+    /// Anchored pattern for canonical postal codes
+    pub postal_code: Option<&'static str>,
+    pub required: &'static [AddressField],
+    /// Known regions: canonical form followed by accepted spellings
+    pub regions: Option<&'static [(&'static str, &'static [&'static str])]>,
+}
+
+/// A country rule violation for an address
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressIssue {
+    pub code: &'static str,
+    pub field: AddressField,
+    pub message: String,
+    /// The values named in `message`, for message catalogs
+    pub args: Vec<(&'static str, String)>,
+    /// Normalized replacement value, when one can be derived
+    pub suggestion: Option<String>,
+}
+
+const STREET_CITY_POSTAL: &[AddressField] = &[
+    AddressField::Street,
+    AddressField::Locality,
+    AddressField::PostalCode,
+];
+
+const FULL: &[AddressField] = &[
+    AddressField::Street,
+    AddressField::Locality,
+    AddressField::Region,
+    AddressField::PostalCode,
+];
+
+const US_STATES: &[(&str, &[&str])] = &[
+    ("AL", &["alabama"]),
+    ("AK", &["alaska"]),
+    ("AZ", &["arizona"]),
+    ("AR", &["arkansas"]),
+    ("CA", &["california"]),
+    ("CO", &["colorado"]),
+    ("CT", &["connecticut"]),
+    ("DE", &["delaware"]),
+    ("DC", &["district of columbia", "washington dc"]),
+    ("FL", &["florida"]),
+    ("GA", &["georgia"]),
+    ("HI", &["hawaii"]),
+    ("ID", &["idaho"]),
+    ("IL", &["illinois"]),
+    ("IN", &["indiana"]),
+    ("IA", &["iowa"]),
+    ("KS", &["kansas"]),
+    ("KY", &["kentucky"]),
+    ("LA", &["louisiana"]),
+    ("ME", &["maine"]),
+    ("MD", &["maryland"]),
+    ("MA", &["massachusetts"]),
+    ("MI", &["michigan"]),
+    ("MN", &["minnesota"]),
+    ("MS", &["mississippi"]),
+    ("MO", &["missouri"]),
+    ("MT", &["montana"]),
+    ("NE", &["nebraska"]),
+    ("NV", &["nevada"]),
+    ("NH", &["new hampshire"]),
+    ("NJ", &["new jersey"]),
+    ("NM", &["new mexico"]),
+    ("NY", &["new york"]),
+    ("NC", &["north carolina"]),
+    ("ND", &["north dakota"]),
+    ("OH", &["ohio"]),
+    ("OK", &["oklahoma"]),
+    ("OR", &["oregon"]),
+    ("PA", &["pennsylvania"]),
+    ("RI", &["rhode island"]),
+    ("SC", &["south carolina"]),
+    ("SD", &["south dakota"]),
+    ("TN", &["tennessee"]),
+    ("TX", &["texas"]),
+    ("UT", &["utah"]),
+    ("VT", &["vermont"]),
+    ("VA", &["virginia"]),
+    ("WA", &["washington"]),
+    ("WV", &["west virginia"]),
+    ("WI", &["wisconsin"]),
+    ("WY", &["wyoming"]),
+    ("AS", &["american samoa"]),
+    ("GU", &["guam"]),
+    ("MP", &["northern mariana islands"]),
+    ("PR", &["puerto rico"]),
+    ("VI", &["virgin islands", "us virgin islands"]),
+    ("AA", &["armed forces americas"]),
+    ("AE", &["armed forces europe"]),
+    ("AP", &["armed forces pacific"]),
+];
+
+const CA_PROVINCES: &[(&str, &[&str])] = &[
+    ("AB", &["alberta"]),
+    ("BC", &["british columbia", "colombie-britannique"]),
+    ("MB", &["manitoba"]),
+    ("NB", &["new brunswick", "nouveau-brunswick"]),
+    (
+        "NL",
+        &["newfoundland and labrador", "terre-neuve-et-labrador"],
+    ),
+    ("NS", &["nova scotia", "nouvelle-écosse"]),
+    (
+        "NT",
+        &["northwest territories", "territoires du nord-ouest"],
+    ),
+    ("NU", &["nunavut"]),
+    ("ON", &["ontario"]),
+    ("PE", &["prince edward island", "île-du-prince-édouard"]),
+    ("QC", &["quebec", "québec"]),
+    ("SK", &["saskatchewan"]),
+    ("YT", &["yukon"]),
+];
+
+const AU_STATES: &[(&str, &[&str])] = &[
+    ("ACT", &["australian capital territory"]),
+    ("NSW", &["new south wales"]),
+    ("NT", &["northern territory"]),
+    ("QLD", &["queensland"]),
+    ("SA", &["south australia"]),
+    ("TAS", &["tasmania"]),
+    ("VIC", &["victoria"]),
+    ("WA", &["western australia"]),
+];
+
+const JP_PREFECTURES: &[(&str, &[&str])] = &[
+    ("北海道", &["hokkaido", "jp-01"]),
+    ("青森県", &["aomori", "青森", "jp-02"]),
+    ("岩手県", &["iwate", "岩手", "jp-03"]),
+    ("宮城県", &["miyagi", "宮城", "jp-04"]),
+    ("秋田県", &["akita", "秋田", "jp-05"]),
+    ("山形県", &["yamagata", "山形", "jp-06"]),
+    ("福島県", &["fukushima", "福島", "jp-07"]),
+    ("茨城県", &["ibaraki", "茨城", "jp-08"]),
+    ("栃木県", &["tochigi", "栃木", "jp-09"]),
+    ("群馬県", &["gunma", "群馬", "jp-10"]),
+    ("埼玉県", &["saitama", "埼玉", "jp-11"]),
+    ("千葉県", &["chiba", "千葉", "jp-12"]),
+    ("東京都", &["tokyo", "東京", "jp-13"]),
+    ("神奈川県", &["kanagawa", "神奈川", "jp-14"]),
+    ("新潟県", &["niigata", "新潟", "jp-15"]),
+    ("富山県", &["toyama", "富山", "jp-16"]),
+    ("石川県", &["ishikawa", "石川", "jp-17"]),
+    ("福井県", &["fukui", "福井", "jp-18"]),
+    ("山梨県", &["yamanashi", "山梨", "jp-19"]),
+    ("長野県", &["nagano", "長野", "jp-20"]),
+    ("岐阜県", &["gifu", "岐阜", "jp-21"]),
+    ("静岡県", &["shizuoka", "静岡", "jp-22"]),
+    ("愛知県", &["aichi", "愛知", "jp-23"]),
+    ("三重県", &["mie", "三重", "jp-24"]),
+    ("滋賀県", &["shiga", "滋賀", "jp-25"]),
+    ("京都府", &["kyoto", "京都", "jp-26"]),
+    ("大阪府", &["osaka", "大阪", "jp-27"]),
+    ("兵庫県", &["hyogo", "兵庫", "jp-28"]),
+    ("奈良県", &["nara", "奈良", "jp-29"]),
+    ("和歌山県", &["wakayama", "和歌山", "jp-30"]),
+    ("鳥取県", &["tottori", "鳥取", "jp-31"]),
+    ("島根県", &["shimane", "島根", "jp-32"]),
+    ("岡山県", &["okayama", "岡山", "jp-33"]),
+    ("広島県", &["hiroshima", "広島", "jp-34"]),
+    ("山口県", &["yamaguchi", "山口", "jp-35"]),
+    ("徳島県", &["tokushima", "徳島", "jp-36"]),
+    ("香川県", &["kagawa", "香川", "jp-37"]),
+    ("愛媛県", &["ehime", "愛媛", "jp-38"]),
+    ("高知県", &["kochi", "高知", "jp-39"]),
+    ("福岡県", &["fukuoka", "福岡", "jp-40"]),
+    ("佐賀県", &["saga", "佐賀", "jp-41"]),
+    ("長崎県", &["nagasaki", "長崎", "jp-42"]),
+    ("熊本県", &["kumamoto", "熊本", "jp-43"]),
+    ("大分県", &["oita", "大分", "jp-44"]),
+    ("宮崎県", &["miyazaki", "宮崎", "jp-45"]),
+    ("鹿児島県", &["kagoshima", "鹿児島", "jp-46"]),
+    ("沖縄県", &["okinawa", "沖縄", "jp-47"]),
+];
+
+pub const COUNTRY_RULES: &[CountryRules] = &[
+    CountryRules {
+        country: "US",
+        postal_code: Some(r"^\d{5}(-\d{4})?$"),
+        required: FULL,
+        regions: Some(US_STATES),
+    },
+    CountryRules {
+        country: "CA",
+        postal_code: Some(r"^[ABCEGHJ-NPRSTVXY]\d[ABCEGHJ-NPRSTV-Z] \d[ABCEGHJ-NPRSTV-Z]\d$"),
+        required: FULL,
+        regions: Some(CA_PROVINCES),
+    },
+    CountryRules {
+        country: "AU",
+        postal_code: Some(r"^\d{4}$"),
+        required: FULL,
+        regions: Some(AU_STATES),
+    },
+    CountryRules {
+        country: "JP",
+        postal_code: Some(r"^\d{3}-\d{4}$"),
+        required: &[
+            AddressField::Region,
+            AddressField::Locality,
+            AddressField::PostalCode,
+        ],
+        regions: Some(JP_PREFECTURES),
+    },
+    CountryRules {
+        country: "GB",
+        postal_code: Some(r"^(GIR 0AA|[A-Z]{1,2}\d[A-Z\d]? \d[A-Z]{2})$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "FR",
+        postal_code: Some(r"^\d{5}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "DE",
+        postal_code: Some(r"^\d{5}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "ES",
+        postal_code: Some(r"^(0[1-9]|[1-4]\d|5[0-2])\d{3}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "IT",
+        postal_code: Some(r"^\d{5}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "NL",
+        postal_code: Some(r"^[1-9]\d{3} [A-Z]{2}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "PL",
+        postal_code: Some(r"^\d{2}-\d{3}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "BR",
+        postal_code: Some(r"^\d{5}-\d{3}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+    CountryRules {
+        country: "CN",
+        postal_code: Some(r"^\d{6}$"),
+        required: &[AddressField::Region, AddressField::Locality],
+        regions: None,
+    },
+    CountryRules {
+        country: "IN",
+        postal_code: Some(r"^[1-9]\d{5}$"),
+        required: STREET_CITY_POSTAL,
+        regions: None,
+    },
+];
+
+lazy_static! {
+    static ref POSTAL_PATTERNS: Vec<(&'static str, Regex)> = COUNTRY_RULES
+        .iter()
+        .filter_map(|rules| {
+            rules
+                .postal_code
+                .map(|p| (rules.country, Regex::new(p).expect("valid postal pattern")))
+        })
+        .collect();
+}
+
+/// Look up the rules for an ISO-3166 alpha-2 country code
+pub fn rules_for_country(country: &str) -> Option<&'static CountryRules> {
+    COUNTRY_RULES
+        .iter()
+        .find(|rules| rules.country.eq_ignore_ascii_case(country.trim()))
+}
+
+/// Check address components against the rules of their `country`
+pub fn validate_components(components: &AddressComponents) -> Vec<AddressIssue> {
+    let mut issues = Vec::new();
+
+    let Some(rules) = components.country.as_deref().and_then(rules_for_country) else {
+        return issues;
+    };
+
+    for field in rules.required {
+        if field.value(components).is_none() {
+            issues.push(AddressIssue {
+                code: "address-component-missing",
+                field: *field,
+                message: format!(
+                    "Addresses in {} require a {} component",
+                    rules.country,
+                    field.as_str()
+                ),
+                args: vec![
+                    ("country", rules.country.to_string()),
+                    ("field", field.as_str().to_string()),
+                ],
+                suggestion: None,
+            });
+        }
+    }
+
+    if let Some(postal_code) = AddressField::PostalCode.value(components) {
+        check_postal_code(rules, postal_code, &mut issues);
+    }
+
+    if let (Some(regions), Some(region)) = (rules.regions, AddressField::Region.value(components)) {
+        check_region(rules.country, regions, region, &mut issues);
+    }
+
+    issues
+}
+
+/// Normalize a postal code to the country's canonical form, if it can be
+pub fn normalize_postal_code(country: &str, postal_code: &str) -> Option<String> {
+    let rules = rules_for_country(country)?;
+    let pattern = postal_pattern(rules.country)?;
+
+    let compact: String = postal_code
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_uppercase();
+    if !compact.is_ascii() {
+        return None;
+    }
+
+    let candidate = match rules.country {
+        // Outward and inward codes separated by a space; the inward code is always 3 chars
+        "GB" | "CA" if compact.len() > 3 => {
+            format!(
+                "{} {}",
+                &compact[..compact.len() - 3],
+                &compact[compact.len() - 3..]
+            )
+        }
+        "NL" if compact.len() == 6 => format!("{} {}", &compact[..4], &compact[4..]),
+        "JP" | "BR" if compact.len() == 7 || compact.len() == 8 => {
+            let split = compact.len() - if rules.country == "JP" { 4 } else { 3 };
+            format!("{}-{}", &compact[..split], &compact[split..])
+        }
+        "PL" if compact.len() == 5 => format!("{}-{}", &compact[..2], &compact[2..]),
+        "US" if compact.len() == 9 => format!("{}-{}", &compact[..5], &compact[5..]),
+        _ => compact,
+    };
+
+    pattern.is_match(&candidate).then_some(candidate)
+}
+
+fn postal_pattern(country: &str) -> Option<&'static Regex> {
+    POSTAL_PATTERNS
+        .iter()
+        .find(|(c, _)| *c == country)
+        .map(|(_, re)| re)
+}
+
+fn check_postal_code(rules: &CountryRules, postal_code: &str, issues: &mut Vec<AddressIssue>) {
+    let Some(pattern) = postal_pattern(rules.country) else {
+        return;
+    };
+
+    if pattern.is_match(postal_code) {
+        return;
+    }
+
+    match normalize_postal_code(rules.country, postal_code) {
+        Some(normalized) => issues.push(AddressIssue {
+            code: "address-postal-code-format",
+            field: AddressField::PostalCode,
+            message: format!(
+                "Postal code for {} should be written as {}",
+                rules.country, normalized
+            ),
+            args: vec![
+                ("country", rules.country.to_string()),
+                ("postalCode", postal_code.to_string()),
+                ("suggestion", normalized.clone()),
+            ],
+            suggestion: Some(normalized),
+        }),
+        None => issues.push(AddressIssue {
+            code: "address-postal-code-invalid",
+            field: AddressField::PostalCode,
+            message: format!("Invalid postal code for {}: {}", rules.country, postal_code),
+            args: vec![
+                ("country", rules.country.to_string()),
+                ("postalCode", postal_code.to_string()),
+            ],
+            suggestion: None,
+        }),
+    }
+}
+
+fn check_region(
+    country: &str,
+    regions: &[(&str, &[&str])],
+    region: &str,
+    issues: &mut Vec<AddressIssue>,
+) {
+    let region = region.trim();
+    if regions.iter().any(|(canonical, _)| *canonical == region) {
+        return;
+    }
+
+    // Full ISO-3166-2 codes such as "US-CA" or "JP-13" are always accepted
+    let iso_subdivision = region
+        .split_once('-')
+        .filter(|(prefix, _)| prefix.eq_ignore_ascii_case(country))
+        .is_some_and(|(_, sub)| registry::is_subdivision_code(country, &sub.to_uppercase()));
+    if iso_subdivision {
+        return;
+    }
+
+    let folded = region.to_lowercase();
+    let matched = regions.iter().find(|(canonical, names)| {
+        canonical.to_lowercase() == folded || names.contains(&folded.as_str())
+    });
+
+    match matched {
+        Some((canonical, _)) => issues.push(AddressIssue {
+            code: "address-region-format",
+            field: AddressField::Region,
+            message: format!("Region for {} should be written as {}", country, canonical),
+            args: vec![
+                ("country", country.to_string()),
+                ("region", region.to_string()),
+                ("suggestion", canonical.to_string()),
+            ],
+            suggestion: Some(canonical.to_string()),
+        }),
+        None => issues.push(AddressIssue {
+            code: "address-region-unknown",
+            field: AddressField::Region,
+            message: format!("Unknown region for {}: {}", country, region),
+            args: vec![
+                ("country", country.to_string()),
+                ("region", region.to_string()),
+            ],
+            suggestion: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn components(country: &str, region: &str, postal_code: &str) -> AddressComponents {
+        AddressComponents {
+            street: Some("1 Main St".to_string()),
+            locality: Some("Somewhere".to_string()),
+            region: Some(region.to_string()),
+            postal_code: Some(postal_code.to_string()),
+            country: Some(country.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_valid_addresses() {
+        assert!(validate_components(&components("US", "CA", "94105")).is_empty());
+        assert!(validate_components(&components("CA", "ON", "K1A 0B1")).is_empty());
+        assert!(validate_components(&components("JP", "東京都", "100-0001")).is_empty());
+        assert!(validate_components(&components("GB", "", "SW1A 1AA")).is_empty());
+    }
+
+    #[test]
+    fn test_postal_code_normalization() {
+        assert_eq!(
+            normalize_postal_code("GB", "sw1a1aa").as_deref(),
+            Some("SW1A 1AA")
+        );
+        assert_eq!(
+            normalize_postal_code("CA", "k1a0b1").as_deref(),
+            Some("K1A 0B1")
+        );
+        assert_eq!(
+            normalize_postal_code("JP", "1000001").as_deref(),
+            Some("100-0001")
+        );
+        assert_eq!(normalize_postal_code("US", "1234"), None);
+
+        let issues = validate_components(&components("GB", "", "sw1a 1aa"));
+        assert_eq!(issues[0].code, "address-postal-code-format");
+        assert_eq!(issues[0].suggestion.as_deref(), Some("SW1A 1AA"));
+    }
+
+    #[test]
+    fn test_region_and_required_checks() {
+        let issues = validate_components(&components("US", "California", "1234"));
+        let codes: Vec<_> = issues.iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            vec!["address-postal-code-invalid", "address-region-format"]
+        );
+        assert_eq!(issues[1].suggestion.as_deref(), Some("CA"));
+
+        assert!(validate_components(&components("US", "US-CA", "94105")).is_empty());
+
+        let issues = validate_components(&components("CA", "Atlantis", "K1A 0B1"));
+        assert_eq!(issues[0].code, "address-region-unknown");
+
+        let mut missing = components("FR", "", "75001");
+        missing.street = None;
+        let issues = validate_components(&missing);
+        assert_eq!(issues[0].code, "address-component-missing");
+        assert_eq!(issues[0].field, AddressField::Street);
+    }
+}
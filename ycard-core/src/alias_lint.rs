@@ -0,0 +1,363 @@
+//! Consistency checks for alias packs.
+//!
+//! Lookups fold case and diacritics, so `telephone` and `téléphone` are the
+//! same alias, and two spellings that fold alike but map to different
+//! fields make the result depend on hash order. The linter compares every
+//! table after folding, within a locale, across locales and across packs
+//! layered on top of each other.
+//!
+//! Diagnostic paths are JSON pointers into the list of packs, e.g.
+//! `/0/locales/fr/keyAliases/travail`.
+
+use crate::generated_schema::{ENUM_VARIANTS, ROOT_FIELDS};
+use crate::i18n::{normalize_key, AliasPack};
+use crate::locale::LanguageId;
+use crate::parser::is_name_field;
+use crate::registry::{canonicalize_language_tag, is_country_code};
+use crate::rules::diagnostic;
+use crate::validator::{Diagnostic, DiagnosticLevel};
+use std::collections::{BTreeMap, BTreeSet};
+
+const TABLES: [&str; 3] = ["keyAliases", "typeAliases", "countries"];
+
+/// The entry lookups use for one alias, once all packs are layered
+struct Winner {
+    pack: usize,
+    alias: String,
+    target: String,
+}
+
+/// (locale, table, folded alias) -> winning entry
+type Winners<'a> = BTreeMap<(String, &'a str, String), Winner>;
+
+/// Lint `packs` in load order; later packs override earlier ones
+pub fn lint_packs(packs: &[AliasPack]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut winners = Winners::new();
+
+    for (index, pack) in packs.iter().enumerate() {
+        let locales: BTreeMap<_, _> = pack.locales.iter().collect();
+        for (locale, data) in locales {
+            let base = format!("/{}/locales/{}", index, escape(locale));
+            lint_locale_tag(locale, &base, &mut diagnostics);
+
+            let mut folded_keys = BTreeMap::new();
+            for table in TABLES {
+                let entries = match table {
+                    "keyAliases" => Some(&data.key_aliases),
+                    "typeAliases" => Some(&data.type_aliases),
+                    _ => data.countries.as_ref(),
+                };
+                let Some(entries) = entries else {
+                    continue;
+                };
+
+                // Folded alias -> spellings, sorted for stable output
+                let mut groups: BTreeMap<String, Vec<(&String, &String)>> = BTreeMap::new();
+                for (alias, target) in entries {
+                    groups
+                        .entry(normalize_key(alias))
+                        .or_default()
+                        .push((alias, target));
+                }
+
+                for (folded, mut spellings) in groups {
+                    spellings.sort();
+                    for (alias, target) in &spellings {
+                        let path = format!("{}/{}/{}", base, table, escape(alias));
+                        lint_target(table, alias, target, &path, &mut diagnostics);
+                    }
+                    lint_spellings(locale, table, &folded, &spellings, &base, &mut diagnostics);
+
+                    let (alias, target) = spellings[0];
+                    if table == "keyAliases" {
+                        folded_keys.insert(folded.clone(), target.clone());
+                    } else if table == "typeAliases" {
+                        if let Some(key_target) = folded_keys.get(&folded) {
+                            diagnostics.push(at(
+                                DiagnosticLevel::Info,
+                                "alias-key-type-overlap",
+                                format!(
+                                    "\"{}\" in {} is both a key alias (-> {}) and a type alias (-> {})",
+                                    alias, locale, key_target, target
+                                ),
+                                format!("{}/{}/{}", base, table, escape(alias)),
+                            ));
+                        }
+                    }
+
+                    let key = (locale.clone(), table, folded);
+                    if let Some(previous) = winners.get(&key) {
+                        if &previous.target != target {
+                            diagnostics.push(at(
+                                DiagnosticLevel::Warning,
+                                "alias-overridden",
+                                format!(
+                                    "\"{}\" in {} maps to {} here, overriding {} from pack {} (\"{}\")",
+                                    alias,
+                                    locale,
+                                    target,
+                                    previous.target,
+                                    previous.pack,
+                                    previous.alias
+                                ),
+                                format!("{}/{}/{}", base, table, escape(alias)),
+                            ));
+                        }
+                    }
+                    winners.insert(
+                        key,
+                        Winner {
+                            pack: index,
+                            alias: alias.clone(),
+                            target: target.clone(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    lint_across_locales(&winners, &mut diagnostics);
+    diagnostics
+}
+
+fn lint_locale_tag(locale: &str, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if locale == "root" {
+        return;
+    }
+    let message = match canonicalize_language_tag(locale) {
+        Err(error) => format!("Locale \"{}\" is not a BCP-47 tag: {}", locale, error),
+        Ok(canonical) if canonical != locale => {
+            format!("Locale \"{}\" should be written \"{}\"", locale, canonical)
+        }
+        Ok(_) if LanguageId::parse(locale).is_ok() => return,
+        Ok(_) => format!("Locale \"{}\" names no language", locale),
+    };
+    diagnostics.push(at(
+        DiagnosticLevel::Warning,
+        "alias-locale-invalid",
+        message,
+        path.to_string(),
+    ));
+}
+
+fn lint_target(
+    table: &str,
+    alias: &str,
+    target: &str,
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let (known, what) = match table {
+        "keyAliases" => (
+            ROOT_FIELDS.iter().any(|f| f.name == target) || is_name_field(target),
+            "field",
+        ),
+        "typeAliases" => (
+            ENUM_VARIANTS
+                .iter()
+                .filter(|(name, _)| matches!(*name, "PhoneType" | "EmailType" | "AddressType"))
+                .any(|(_, variants)| variants.contains(&target)),
+            "type",
+        ),
+        _ => (is_country_code(target), "ISO-3166-1 alpha-2 code"),
+    };
+    if !known {
+        let code = if table == "countries" {
+            "alias-country-invalid"
+        } else {
+            "alias-target-unknown"
+        };
+        diagnostics.push(at(
+            DiagnosticLevel::Error,
+            code,
+            format!(
+                "\"{}\" maps to \"{}\", which is not a {}",
+                alias, target, what
+            ),
+            path.to_string(),
+        ));
+    }
+}
+
+/// Spellings of one alias that fold to the same lookup key
+fn lint_spellings(
+    locale: &str,
+    table: &str,
+    folded: &str,
+    spellings: &[(&String, &String)],
+    base: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if spellings.len() < 2 {
+        return;
+    }
+    let targets: BTreeSet<&String> = spellings.iter().map(|(_, t)| *t).collect();
+    if targets.len() > 1 {
+        let listed = spellings
+            .iter()
+            .map(|(alias, target)| format!("\"{}\" -> {}", alias, target))
+            .collect::<Vec<_>>()
+            .join(", ");
+        for (alias, _) in spellings {
+            diagnostics.push(at(
+                DiagnosticLevel::Error,
+                "alias-conflict",
+                format!(
+                    "Spellings in {} that match alike map to different values: {}",
+                    locale, listed
+                ),
+                format!("{}/{}/{}", base, table, escape(alias)),
+            ));
+        }
+        return;
+    }
+
+    // The folded spelling is the one to keep; otherwise the first
+    let keep = spellings
+        .iter()
+        .position(|(alias, _)| alias.as_str() == folded)
+        .unwrap_or(0);
+    for (i, (alias, _)) in spellings.iter().enumerate() {
+        if i != keep {
+            diagnostics.push(at(
+                DiagnosticLevel::Hint,
+                "alias-redundant",
+                format!(
+                    "\"{}\" in {} already matches \"{}\", ignoring case and diacritics",
+                    alias, locale, spellings[keep].0
+                ),
+                format!("{}/{}/{}", base, table, escape(alias)),
+            ));
+        }
+    }
+}
+
+/// One alias meaning different things in different locales: input in the
+/// wrong locale, or a detected locale, changes how a document reads
+fn lint_across_locales(winners: &Winners<'_>, diagnostics: &mut Vec<Diagnostic>) {
+    let mut by_alias: BTreeMap<(&str, &str), Vec<(&str, &Winner)>> = BTreeMap::new();
+    for ((locale, table, folded), winner) in winners {
+        if *table != "countries" {
+            by_alias
+                .entry((table, folded.as_str()))
+                .or_default()
+                .push((locale.as_str(), winner));
+        }
+    }
+
+    for ((table, _), entries) in by_alias {
+        let targets: BTreeSet<&String> = entries.iter().map(|(_, w)| &w.target).collect();
+        if targets.len() < 2 {
+            continue;
+        }
+        let listed = entries
+            .iter()
+            .map(|(locale, w)| format!("{} -> {}", locale, w.target))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let (locale, Winner { pack, alias, .. }) = entries[0];
+        diagnostics.push(at(
+            DiagnosticLevel::Info,
+            "alias-ambiguous",
+            format!("\"{}\" means different things by locale: {}", alias, listed),
+            format!(
+                "/{}/locales/{}/{}/{}",
+                pack,
+                escape(locale),
+                table,
+                escape(alias)
+            ),
+        ));
+    }
+}
+
+fn at(level: DiagnosticLevel, code: &str, message: String, path: String) -> Diagnostic {
+    Diagnostic {
+        path: Some(path),
+        ..diagnostic(level, code, message)
+    }
+}
+
+/// JSON pointer escaping of one reference token
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack(json: &str) -> AliasPack {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<(&str, &str)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.code.as_deref().unwrap(), d.path.as_deref().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_within_pack() {
+        let diagnostics = lint_packs(&[pack(
+            r#"{"version": "1", "locales": {
+                "fr": {
+                    "keyAliases": {"telephone": "phones", "téléphone": "phones",
+                                   "Courriel": "emails", "courriel": "manager",
+                                   "travail": "phones", "bidule": "gadgets"},
+                    "typeAliases": {"travail": "work"},
+                    "countries": {"allemagne": "DE", "atlantide": "ZZ"}
+                },
+                "fr_be": {"keyAliases": {}, "typeAliases": {}}
+            }}"#,
+        )]);
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ("alias-target-unknown", "/0/locales/fr/keyAliases/bidule"),
+                ("alias-conflict", "/0/locales/fr/keyAliases/Courriel"),
+                ("alias-conflict", "/0/locales/fr/keyAliases/courriel"),
+                ("alias-redundant", "/0/locales/fr/keyAliases/téléphone"),
+                (
+                    "alias-key-type-overlap",
+                    "/0/locales/fr/typeAliases/travail"
+                ),
+                ("alias-country-invalid", "/0/locales/fr/countries/atlantide"),
+                ("alias-locale-invalid", "/0/locales/fr_be"),
+            ]
+        );
+        assert_eq!(diagnostics[1].level, DiagnosticLevel::Error);
+        assert_eq!(diagnostics[3].level, DiagnosticLevel::Hint);
+    }
+
+    #[test]
+    fn test_lint_across_packs_and_locales() {
+        let base = pack(
+            r#"{"version": "1", "locales": {
+                "es": {"keyAliases": {"correo": "emails"}, "typeAliases": {}},
+                "it": {"keyAliases": {"posta": "emails"}, "typeAliases": {}}
+            }}"#,
+        );
+        let overlay = pack(
+            r#"{"version": "2", "locales": {
+                "es": {"keyAliases": {"correo": "emails"}, "typeAliases": {}},
+                "pt": {"keyAliases": {"posta": "addresses"}, "typeAliases": {}},
+                "it": {"keyAliases": {"posta": "manager"}, "typeAliases": {}}
+            }}"#,
+        );
+        let diagnostics = lint_packs(&[base, overlay]);
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ("alias-overridden", "/1/locales/it/keyAliases/posta"),
+                ("alias-ambiguous", "/1/locales/it/keyAliases/posta"),
+            ]
+        );
+        assert!(diagnostics[1]
+            .message
+            .contains("it -> manager, pt -> addresses"));
+    }
+}
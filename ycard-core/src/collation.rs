@@ -0,0 +1,267 @@
+//! Locale-aware ordering of cards by name.
+//!
+//! [`YCard::sort_key`] picks the text a card sorts under for a locale and
+//! [`Collator`] orders the keys. With the `icu` feature, the text is compared
+//! with ICU's collator for the locale, including the tailorings a `-u-co-`
+//! extension selects: `de-u-co-phonebk` sorts `Müller` as `Mueller` and
+//! `es-u-co-trad` sorts `ch` after `cz`.
+//!
+//! Without it, the key approximates the same orders. Case, diacritics and
+//! voicing marks only break ties, katakana sorts with hiragana, and letters
+//! a language sorts on their own (Spanish `ñ`, Swedish `å`, `ä` and `ö`)
+//! follow the letter they are built on, or `z`.
+
+use crate::locale::LanguageId;
+use crate::schema::YCard;
+use std::cmp::Ordering;
+use unicode_normalization::UnicodeNormalization;
+
+/// The text a card sorts under and its collation key for one locale.
+///
+/// Sort keys have no order of their own: compare them with
+/// [`Collator::compare`], which uses ICU's collator with the `icu` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    /// The name the card sorts under: `Doe Jane`, `やまだ はなこ`
+    pub text: String,
+    /// `text` folded and tailored to the locale, in bytewise order. This
+    /// approximates the locale's collation and is what [`Collator`] compares
+    /// without `icu`; ICU's order can differ from it.
+    pub key: String,
+}
+
+impl SortKey {
+    pub fn new(text: &str, locale: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            key: collation_key(text, locale),
+        }
+    }
+}
+
+/// Orders cards by name for readers of one locale
+pub struct Collator {
+    locale: String,
+    #[cfg(feature = "icu")]
+    icu: icu_collator::Collator,
+}
+
+impl Collator {
+    pub fn new(locale: &str) -> Self {
+        Self {
+            locale: locale.to_string(),
+            #[cfg(feature = "icu")]
+            icu: icu_collator(locale),
+        }
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// The key `ycard` sorts under, if it has a name
+    pub fn sort_key(&self, ycard: &YCard) -> Option<SortKey> {
+        ycard.sort_key(&self.locale)
+    }
+
+    /// Compare two sort keys, breaking collation ties by their text
+    pub fn compare(&self, a: &SortKey, b: &SortKey) -> Ordering {
+        #[cfg(feature = "icu")]
+        let order = self.icu.compare(&a.text, &b.text);
+
+        #[cfg(not(feature = "icu"))]
+        let order = a.key.cmp(&b.key);
+
+        order.then_with(|| a.text.cmp(&b.text))
+    }
+
+    /// Sort cards by name. Cards without a name go last, and cards that
+    /// sort the same keep their order.
+    pub fn sort(&self, cards: &mut Vec<YCard>) {
+        self.sort_by_card(cards, |card| card)
+    }
+
+    /// Sort items by the name of the card each one holds
+    pub fn sort_by_card<T>(&self, items: &mut Vec<T>, card: impl Fn(&T) -> &YCard) {
+        let mut keyed: Vec<(Option<SortKey>, T)> = items
+            .drain(..)
+            .map(|item| (self.sort_key(card(&item)), item))
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+            (Some(a), Some(b)) => self.compare(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        items.extend(keyed.into_iter().map(|(_, item)| item));
+    }
+}
+
+/// Sort cards by name for readers of `locale`
+pub fn sort_cards(cards: &mut Vec<YCard>, locale: &str) {
+    Collator::new(locale).sort(cards)
+}
+
+#[cfg(feature = "icu")]
+fn icu_collator(locale: &str) -> icu_collator::Collator {
+    use icu_collator::CollatorOptions;
+    use icu_locid::Locale;
+
+    // Unknown or malformed locales get the root collation
+    let locale: Locale = crate::registry::canonicalize_language_tag(locale)
+        .ok()
+        .and_then(|tag| tag.parse().ok())
+        .unwrap_or_default();
+    icu_collator::Collator::try_new(&(&locale).into(), CollatorOptions::new())
+        .or_else(|_| icu_collator::Collator::try_new(&Default::default(), CollatorOptions::new()))
+        .expect("root collation data is compiled in")
+}
+
+/// The `co` keyword of a tag's `-u-` extension: `phonebk` in `de-u-co-phonebk`
+fn collation_type(locale: &str) -> Option<String> {
+    let subtags: Vec<String> = locale.split(['-', '_']).map(str::to_lowercase).collect();
+    let extension = subtags.iter().position(|s| s == "u")?;
+    let mut keywords = subtags[extension + 1..]
+        .iter()
+        .take_while(|s| s.len() > 1)
+        .skip_while(|s| *s != "co");
+    keywords.next()?;
+    keywords.next().cloned()
+}
+
+/// A key that orders names bytewise roughly as the locale's collation would
+fn collation_key(text: &str, locale: &str) -> String {
+    let language = LanguageId::parse(locale)
+        .map(|id| id.language)
+        .unwrap_or_default();
+
+    // Letters the language sorts apart from their base letter, rewritten
+    // before diacritics are dropped. `~` sorts after every ASCII letter.
+    let letters: &[(&str, &str)] = match (language.as_str(), collation_type(locale).as_deref()) {
+        ("de", Some("phonebk")) => &[("ä", "ae"), ("ö", "oe"), ("ü", "ue")],
+        ("es", Some("trad")) => &[("ch", "c~"), ("ll", "l~"), ("ñ", "n~")],
+        ("es", _) => &[("ñ", "n~")],
+        ("sv" | "fi", _) => &[
+            ("å", "z~a"),
+            ("ä", "z~b"),
+            ("æ", "z~b"),
+            ("ö", "z~c"),
+            ("ø", "z~c"),
+        ],
+        ("da" | "nb" | "nn" | "no", _) => &[("æ", "z~a"), ("ø", "z~b"), ("å", "z~c")],
+        _ => &[],
+    };
+    let mut tailored = text.nfc().collect::<String>().to_lowercase();
+    for (letter, key) in letters {
+        tailored = tailored.replace(letter, key);
+    }
+    tailored = tailored.replace('ß', "ss");
+
+    let mut key = String::with_capacity(tailored.len());
+    for c in tailored.nfd() {
+        match c {
+            // Diacritics and kana voicing marks
+            '\u{0300}'..='\u{036F}' | '\u{3099}' | '\u{309A}' => {}
+            // Katakana sorts with the matching hiragana
+            'ァ'..='ヶ' => key.extend(char::from_u32(c as u32 - 0x60)),
+            '~' => key.push(c),
+            c if c.is_alphanumeric() => key.push(c),
+            // Words are separated by a single space, which sorts first
+            _ => {
+                if !key.is_empty() && !key.ends_with(' ') {
+                    key.push(' ');
+                }
+            }
+        }
+    }
+    key.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Name;
+
+    fn card(family: &str, given: &str) -> YCard {
+        YCard {
+            name: Some(Name {
+                family_name: Some(vec![family.to_string()]),
+                given_name: Some(vec![given.to_string()]),
+                ..Name::default()
+            }),
+            ..YCard::default()
+        }
+    }
+
+    fn sorted(mut cards: Vec<YCard>, locale: &str) -> Vec<String> {
+        sort_cards(&mut cards, locale);
+        cards
+            .iter()
+            .map(|card| card.sort_name(locale).unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_collation_key() {
+        assert_eq!(collation_key("Doe-Smith, Jane", "en"), "doe smith jane");
+        assert_eq!(collation_key("Müller", "de"), "muller");
+        assert_eq!(collation_key("Müller", "de-u-co-phonebk"), "mueller");
+        assert_eq!(collation_key("Straße", "de"), "strasse");
+        assert_eq!(collation_key("ヤマダ", "ja"), collation_key("やまだ", "ja"));
+        assert_eq!(collation_type("es-ES-u-co-trad"), Some("trad".to_string()));
+        assert_eq!(collation_type("es-u-kn-true"), None);
+    }
+
+    #[test]
+    fn test_sort_cards() {
+        // German phonebook order reads umlauts as a vowel plus `e`
+        let cards = || vec![card("Muff", "Anna"), card("Müller", "Karl")];
+        assert_eq!(sorted(cards(), "de"), ["Muff Anna", "Müller Karl"]);
+        assert_eq!(
+            sorted(cards(), "de-u-co-phonebk"),
+            ["Müller Karl", "Muff Anna"]
+        );
+
+        // Modern Spanish sorts `ch` as `c` and `h`, traditional after `cz`;
+        // `ñ` follows `n` in both
+        let cards = || {
+            vec![
+                card("Cuadra", "Ana"),
+                card("Chávez", "Luis"),
+                card("Ñandú", "Eva"),
+                card("Nuñez", "Pilar"),
+            ]
+        };
+        assert_eq!(
+            sorted(cards(), "es"),
+            ["Chávez Luis", "Cuadra Ana", "Nuñez Pilar", "Ñandú Eva"]
+        );
+        assert_eq!(
+            sorted(cards(), "es-u-co-trad"),
+            ["Cuadra Ana", "Chávez Luis", "Nuñez Pilar", "Ñandú Eva"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_reading() {
+        let input = |name: &str, reading: &str| {
+            crate::parse(&format!("name: {name}\nふりがな: {reading}\n"), Some("ja")).unwrap()
+        };
+        let mut cards = vec![
+            input("山田 花子", "やまだ はなこ"),
+            YCard::default(),
+            input("佐藤 一郎", "サトウ イチロウ"),
+            input("伊藤 健", "いとう けん"),
+        ];
+        sort_cards(&mut cards, "ja");
+        let names: Vec<_> = cards
+            .iter()
+            .map(|card| card.sort_name("ja").unwrap_or_default())
+            .collect();
+        // Cards without a name go last
+        assert_eq!(
+            names,
+            ["いとう けん", "サトウ イチロウ", "やまだ はなこ", ""]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Spoofing checks based on UTS #39: confusable skeletons, mixed-script
+//! detection, and bidi control / invisible character scanning.
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_script::{Script, UnicodeScript};
+use unicode_security::{RestrictionLevel, RestrictionLevelDetection};
+
+/// UTS #39 skeleton of `s`. Two strings are confusable when their skeletons match.
+pub fn skeleton(s: &str) -> String {
+    unicode_security::skeleton(s).collect()
+}
+
+/// Are `a` and `b` visually confusable (identical skeletons)?
+pub fn is_confusable(a: &str, b: &str) -> bool {
+    skeleton(a) == skeleton(b)
+}
+
+/// If `s` is non-ASCII but its skeleton is pure ASCII, the ASCII text it imitates
+/// (`pаypal` with a Cyrillic `а` -> `paypal`)
+pub fn ascii_lookalike(s: &str) -> Option<String> {
+    if s.is_ascii() {
+        return None;
+    }
+    let skeleton = skeleton(s);
+    skeleton.is_ascii().then_some(skeleton)
+}
+
+/// Scripts used by a word that exceeds the UTS #39 "highly restrictive" level,
+/// e.g. `["Latin", "Cyrillic"]`; `None` when the mix is acceptable.
+///
+/// Latin combined with Han/Hiragana/Katakana, Han/Bopomofo or Han/Hangul is allowed.
+pub fn mixed_scripts(word: &str) -> Option<Vec<&'static str>> {
+    if word
+        .nfc()
+        .collect::<String>()
+        .as_str()
+        .check_restriction_level(RestrictionLevel::HighlyRestrictive)
+    {
+        return None;
+    }
+
+    let mut scripts: Vec<&'static str> = Vec::new();
+    for c in word.chars() {
+        let script = c.script();
+        if matches!(script, Script::Common | Script::Inherited | Script::Unknown) {
+            continue;
+        }
+        let name = script.full_name();
+        if !scripts.contains(&name) {
+            scripts.push(name);
+        }
+    }
+    // Restricted characters alone (no second script) are not a mix
+    (scripts.len() > 1).then_some(scripts)
+}
+
+/// Unicode bidirectional formatting characters (UAX #9), which can reorder displayed text
+pub fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+/// Characters that render as nothing (or blank) and can hide differences between names
+pub fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}' | '\u{034F}' | '\u{115F}' | '\u{1160}' | '\u{180E}' | '\u{200B}'
+    ) || matches!(
+        c,
+        '\u{2060}'..='\u{2064}' | '\u{3164}' | '\u{FEFF}' | '\u{FFA0}'
+    )
+}
+
+/// Zero-width joiners are required by some scripts (Persian, Indic, emoji
+/// sequences), so they only count as invisible between ASCII characters
+fn is_stray_joiner(prev: Option<char>, c: char, next: Option<char>) -> bool {
+    matches!(c, '\u{200C}' | '\u{200D}')
+        && prev.is_none_or(|p| p.is_ascii())
+        && next.is_none_or(|n| n.is_ascii())
+}
+
+/// Whether `chars[i]` is invisible where it stands
+fn is_invisible_at(chars: &[char], i: usize) -> bool {
+    let prev = i.checked_sub(1).map(|p| chars[p]);
+    is_invisible(chars[i]) || is_stray_joiner(prev, chars[i], chars.get(i + 1).copied())
+}
+
+/// Invisible characters in `s`, including stray zero-width joiners
+pub fn invisible_chars(s: &str) -> Vec<char> {
+    let chars: Vec<char> = s.chars().collect();
+    (0..chars.len())
+        .filter(|&i| is_invisible_at(&chars, i))
+        .map(|i| chars[i])
+        .collect()
+}
+
+/// Remove the characters [`invisible_chars`] reports, by position, so a
+/// joiner that a script needs elsewhere in `s` is kept
+pub fn strip_invisible(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    (0..chars.len())
+        .filter(|&i| !is_invisible_at(&chars, i))
+        .map(|i| chars[i])
+        .collect()
+}
+
+/// Remove bidi controls
+pub fn strip_bidi(s: &str) -> String {
+    s.chars().filter(|c| !is_bidi_control(*c)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skeleton_comparison() {
+        assert!(is_confusable("paypal", "p\u{0430}ypal"));
+        assert!(is_confusable("rn", "m"));
+        assert!(!is_confusable("paypal", "paypai"));
+        assert_eq!(ascii_lookalike("p\u{0430}ypal").as_deref(), Some("paypal"));
+        assert_eq!(ascii_lookalike("bücher"), None);
+        assert_eq!(ascii_lookalike("example"), None);
+    }
+
+    #[test]
+    fn test_mixed_scripts() {
+        assert_eq!(
+            mixed_scripts("p\u{0430}ypal"),
+            Some(vec!["Latin", "Cyrillic"])
+        );
+        assert_eq!(mixed_scripts("Иван"), None);
+        assert_eq!(mixed_scripts("Tanakaさん"), None);
+        assert_eq!(mixed_scripts("José"), None);
+    }
+
+    #[test]
+    fn test_hidden_characters() {
+        assert!(is_bidi_control('\u{202E}'));
+        assert_eq!(invisible_chars("Ja\u{200B}ne"), vec!['\u{200B}']);
+        assert_eq!(invisible_chars("Ja\u{200D}ne"), vec!['\u{200D}']);
+        // ZWNJ is part of Persian orthography
+        assert!(invisible_chars("می\u{200C}خواهم").is_empty());
+        assert_eq!(strip_bidi("\u{202E}Jane\u{2060}"), "Jane\u{2060}");
+        assert_eq!(strip_invisible("\u{202E}Jane\u{2060}"), "\u{202E}Jane");
+        // The stray joiner goes, the one inside the Persian word stays
+        assert_eq!(
+            strip_invisible("Ja\u{200D}ne می\u{200C}خواهم"),
+            "Jane می\u{200C}خواهم"
+        );
+    }
+}
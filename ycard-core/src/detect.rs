@@ -0,0 +1,247 @@
+//! Guessing the locale of a document when the caller does not name one.
+//!
+//! Evidence, strongest first:
+//! 1. `metadata.locale`, taken as written when it is a valid BCP-47 tag
+//! 2. keys (and `type` values) that only some locales' alias tables know,
+//!    plus the writing systems used in keys and values
+//!
+//! A key that several locales share splits its weight between them, so
+//! `telefono` alone says little while `prénom` points clearly at French.
+
+use crate::i18n::AliasManager;
+use crate::registry::canonicalize_language_tag;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::HashMap;
+use unicode_script::{Script, UnicodeScript};
+
+/// Keys scored per document; the rest add nothing but lookup time
+const MAX_KEYS: usize = 512;
+
+/// Where a detected locale came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LocaleSource {
+    /// The document's `metadata.locale`
+    Metadata,
+    /// Localized keys and the scripts of the text
+    Content,
+    /// The caller's fallback, e.g. `LANG` in the CLI
+    Environment,
+}
+
+/// The most likely locale of a document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocaleGuess {
+    pub locale: String,
+    /// 0.0 (a coin toss) to 1.0 (stated by the document)
+    pub confidence: f64,
+    pub source: LocaleSource,
+}
+
+/// Score `value` against the loaded alias packs and the scripts it uses.
+/// Ties go to the manager's default locale. Returns `None` when the
+/// document holds no locale evidence at all.
+pub fn detect_locale(value: &Value, aliases: &AliasManager) -> Option<LocaleGuess> {
+    if let Some(locale) = metadata_locale(value, aliases) {
+        return Some(LocaleGuess {
+            locale,
+            confidence: 1.0,
+            source: LocaleSource::Metadata,
+        });
+    }
+
+    let mut keys = Vec::new();
+    let mut text = String::new();
+    collect(value, aliases, false, &mut keys, &mut text);
+
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for (word, is_type) in keys.iter().take(MAX_KEYS) {
+        let locales = aliases.locales_knowing(word, *is_type);
+        let weight = 1.0 / locales.len().max(1) as f64;
+        for locale in locales {
+            *scores.entry(locale).or_default() += weight;
+        }
+    }
+    for (locale, weight) in script_votes(&text) {
+        *scores.entry(locale.to_string()).or_default() += weight;
+    }
+
+    let total: f64 = scores.values().sum();
+    let default = aliases.default_locale();
+    let (locale, best) = scores.into_iter().max_by(|(a, x), (b, y)| {
+        x.partial_cmp(y)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            // Prefer the default, then the alphabetically first
+            .then_with(|| (a.as_str() == default).cmp(&(b.as_str() == default)))
+            .then_with(|| b.cmp(a))
+    })?;
+
+    // Share of the evidence, damped when there is little of it
+    let confidence = (best / total) * (1.0 - 0.5f64.powf(best));
+    Some(LocaleGuess {
+        locale,
+        confidence: (confidence * 100.0).round() / 100.0,
+        source: LocaleSource::Content,
+    })
+}
+
+/// The locale named by a POSIX locale variable such as `LANG` or `LC_ALL`
+/// (`fr_CA.UTF-8` -> `fr-CA`). `C` and `POSIX` name no language.
+pub fn locale_from_posix(value: &str) -> Option<String> {
+    let tag = value.split(['.', '@']).next()?.trim();
+    if tag.is_empty() || tag == "C" || tag == "POSIX" {
+        return None;
+    }
+    canonicalize_language_tag(tag).ok()
+}
+
+/// The user's locale from `LC_ALL`, `LC_MESSAGES` or `LANG`, in POSIX
+/// precedence order
+pub fn locale_from_env() -> Option<LocaleGuess> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| locale_from_posix(&value))
+        .map(|locale| LocaleGuess {
+            locale,
+            confidence: 0.5,
+            source: LocaleSource::Environment,
+        })
+}
+
+fn metadata_locale(value: &Value, aliases: &AliasManager) -> Option<String> {
+    let Value::Mapping(map) = value else {
+        return None;
+    };
+    let Value::Mapping(metadata) = field(map, "metadata", aliases)? else {
+        return None;
+    };
+    canonicalize_language_tag(field(metadata, "locale", aliases)?.as_str()?).ok()
+}
+
+/// The value of a canonical field written under its name or a root alias
+fn field<'a>(
+    map: &'a serde_yaml::Mapping,
+    name: &str,
+    aliases: &AliasManager,
+) -> Option<&'a Value> {
+    map.iter().find_map(|(k, v)| {
+        let k = k.as_str()?;
+        (k == name || aliases.resolve_key_alias(k, Some("root")).as_deref() == Some(name))
+            .then_some(v)
+    })
+}
+
+/// Gather keys, `type` values and all text. Keys are paired with whether
+/// they should be looked up as type aliases.
+fn collect(
+    value: &Value,
+    aliases: &AliasManager,
+    in_type: bool,
+    keys: &mut Vec<(String, bool)>,
+    text: &mut String,
+) {
+    match value {
+        Value::Mapping(map) => {
+            for (k, v) in map {
+                let is_type = match k.as_str() {
+                    Some(k) => {
+                        keys.push((k.to_string(), false));
+                        text.push_str(k);
+                        k == "type" || aliases.is_type_key(k)
+                    }
+                    None => false,
+                };
+                collect(v, aliases, is_type, keys, text);
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                collect(item, aliases, in_type, keys, text);
+            }
+        }
+        Value::String(s) => {
+            if in_type {
+                keys.push((s.clone(), true));
+            }
+            text.push_str(s);
+        }
+        Value::Tagged(tagged) => collect(&tagged.value, aliases, in_type, keys, text),
+        _ => {}
+    }
+}
+
+/// Locales suggested by the dominant non-Latin script. Han alone is shared
+/// by Chinese and Japanese; kana settles it.
+fn script_votes(text: &str) -> Vec<(&'static str, f64)> {
+    let mut counts: HashMap<Script, usize> = HashMap::new();
+    for c in text.chars() {
+        *counts.entry(c.script()).or_default() += 1;
+    }
+    let kana =
+        counts.get(&Script::Hiragana).unwrap_or(&0) + counts.get(&Script::Katakana).unwrap_or(&0);
+    if kana > 0 {
+        return vec![("ja", 1.0)];
+    }
+    let dominant = counts
+        .into_iter()
+        .filter(|(script, _)| {
+            !matches!(
+                script,
+                Script::Common | Script::Inherited | Script::Unknown | Script::Latin
+            )
+        })
+        .max_by_key(|(script, count)| (*count, script.full_name()));
+    match dominant.map(|(script, _)| script) {
+        Some(Script::Han) => vec![("zh", 0.5), ("ja", 0.5)],
+        Some(Script::Hangul) => vec![("ko", 1.0)],
+        Some(Script::Cyrillic) => vec![("ru", 1.0)],
+        Some(Script::Arabic) => vec![("ar", 1.0)],
+        Some(Script::Devanagari) => vec![("hi", 1.0)],
+        Some(Script::Thai) => vec![("th", 1.0)],
+        Some(Script::Greek) => vec![("el", 1.0)],
+        Some(Script::Hebrew) => vec![("he", 1.0)],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(input: &str) -> Option<LocaleGuess> {
+        let value: Value = serde_yaml::from_str(input).unwrap();
+        detect_locale(&value, &AliasManager::new())
+    }
+
+    #[test]
+    fn test_detect_from_keys_and_scripts() {
+        let guess = detect("prénom: Jean\nnom_famille: Dupont\ntéléphone: 0612345678\n").unwrap();
+        assert_eq!(guess.locale, "fr");
+        assert_eq!(guess.source, LocaleSource::Content);
+        assert!(guess.confidence > 0.5);
+
+        let guess = detect("名前: 山田太郎\n電話: 0312345678\n").unwrap();
+        assert_eq!(guess.locale, "ja");
+
+        assert_eq!(detect("name: Иван Петров\n").unwrap().locale, "ru");
+
+        // Canonical keys and Latin text carry no evidence
+        assert_eq!(detect("name: Jane\nphones: []\n"), None);
+
+        // The document's own metadata wins
+        let guess = detect("prénom: Jean\nmetadata:\n  locale: fr_CA\n").unwrap();
+        assert_eq!(guess.locale, "fr-CA");
+        assert_eq!(guess.confidence, 1.0);
+    }
+
+    #[test]
+    fn test_locale_from_posix() {
+        assert_eq!(locale_from_posix("fr_CA.UTF-8").as_deref(), Some("fr-CA"));
+        assert_eq!(locale_from_posix("de_DE@euro").as_deref(), Some("de-DE"));
+        assert_eq!(locale_from_posix("C.UTF-8"), None);
+        assert_eq!(locale_from_posix("POSIX"), None);
+    }
+}
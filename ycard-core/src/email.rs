@@ -0,0 +1,290 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+use thiserror::Error;
+
+/// Maximum octets in a local part (RFC 5321 §4.5.3.1.1)
+const MAX_LOCAL_LEN: usize = 64;
+/// Maximum octets in a domain (RFC 5321 §4.5.3.1.2)
+const MAX_DOMAIN_LEN: usize = 255;
+/// Maximum octets in a DNS label (RFC 1035)
+const MAX_LABEL_LEN: usize = 63;
+/// Maximum octets in a forward-path address (RFC 5321 §4.5.3.1.3 minus angle brackets)
+const MAX_ADDRESS_LEN: usize = 254;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum EmailError {
+    #[error("email address is empty")]
+    Empty,
+    #[error("missing '@' separator")]
+    MissingAt,
+    #[error("local part is empty")]
+    LocalEmpty,
+    #[error("local part exceeds {MAX_LOCAL_LEN} octets")]
+    LocalTooLong,
+    #[error("invalid character {0:?} in local part")]
+    LocalInvalidChar(char),
+    #[error("local part has a leading, trailing or repeated dot")]
+    LocalDot,
+    #[error("malformed quoted local part")]
+    QuotedInvalid,
+    #[error("domain is empty")]
+    DomainEmpty,
+    #[error("domain exceeds {MAX_DOMAIN_LEN} octets")]
+    DomainTooLong,
+    #[error("invalid domain label {0:?}")]
+    DomainLabel(String),
+    #[error("domain label {0:?} exceeds {MAX_LABEL_LEN} octets")]
+    DomainLabelTooLong(String),
+    #[error("invalid address literal {0:?}")]
+    IpLiteral(String),
+    #[error("domain {0:?} fails IDNA processing")]
+    Idna(String),
+    #[error("address exceeds {MAX_ADDRESS_LEN} octets")]
+    TooLong,
+}
+
+impl EmailError {
+    /// Diagnostic code reported by the validator for this failure
+    pub fn code(&self) -> &'static str {
+        match self {
+            EmailError::Empty => "email-empty",
+            EmailError::MissingAt => "email-missing-at",
+            EmailError::LocalEmpty => "email-local-empty",
+            EmailError::LocalTooLong => "email-local-too-long",
+            EmailError::LocalInvalidChar(_) => "email-local-invalid-char",
+            EmailError::LocalDot => "email-local-dot",
+            EmailError::QuotedInvalid => "email-quoted-invalid",
+            EmailError::DomainEmpty => "email-domain-empty",
+            EmailError::DomainTooLong => "email-domain-too-long",
+            EmailError::DomainLabel(_) => "email-domain-label-invalid",
+            EmailError::DomainLabelTooLong(_) => "email-domain-label-too-long",
+            EmailError::IpLiteral(_) => "email-ip-literal-invalid",
+            EmailError::Idna(_) => "email-domain-idna",
+            EmailError::TooLong => "email-too-long",
+        }
+    }
+
+    /// The character, label or domain the failure is about, if any
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            EmailError::LocalInvalidChar(c) => Some(c.to_string()),
+            EmailError::DomainLabel(s)
+            | EmailError::DomainLabelTooLong(s)
+            | EmailError::IpLiteral(s)
+            | EmailError::Idna(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// A syntactically valid email address split into its parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailAddress {
+    /// Local part as written (case preserved, quotes kept)
+    pub local: String,
+    /// Domain in lowercased Unicode form (or the address literal)
+    pub domain: String,
+    /// Domain in ASCII (punycode) form, suitable for DNS and SMTP
+    pub ascii_domain: String,
+    /// The local part contains non-ASCII characters and needs SMTPUTF8 (RFC 6531)
+    pub requires_smtputf8: bool,
+}
+
+impl EmailAddress {
+    /// Canonical form: local part untouched, domain lowercased
+    pub fn normalized(&self) -> String {
+        format!("{}@{}", self.local, self.domain)
+    }
+
+    /// ASCII-only form with a punycode domain
+    pub fn to_ascii(&self) -> String {
+        format!("{}@{}", self.local, self.ascii_domain)
+    }
+}
+
+/// Parse and validate an address per RFC 5321/5322, RFC 6531 and UTS #46
+pub fn parse_email(address: &str) -> Result<EmailAddress, EmailError> {
+    if address.is_empty() {
+        return Err(EmailError::Empty);
+    }
+
+    let (local, domain) = split_address(address)?;
+    let requires_smtputf8 = validate_local(local)?;
+    let (domain, ascii_domain) = validate_domain(domain)?;
+
+    if local.len() + 1 + ascii_domain.len() > MAX_ADDRESS_LEN {
+        return Err(EmailError::TooLong);
+    }
+
+    Ok(EmailAddress {
+        local: local.to_string(),
+        domain,
+        ascii_domain,
+        requires_smtputf8,
+    })
+}
+
+/// Validate an address and return its normalized form
+pub fn normalize_email(address: &str) -> Result<String, EmailError> {
+    parse_email(address).map(|email| email.normalized())
+}
+
+fn split_address(address: &str) -> Result<(&str, &str), EmailError> {
+    if address.starts_with('"') {
+        // The quoted string may itself contain '@', so find its closing quote
+        let mut escaped = false;
+        for (i, c) in address.char_indices().skip(1) {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                let (local, rest) = address.split_at(i + 1);
+                return match rest.strip_prefix('@') {
+                    Some(domain) => Ok((local, domain)),
+                    None if rest.is_empty() => Err(EmailError::MissingAt),
+                    None => Err(EmailError::QuotedInvalid),
+                };
+            }
+        }
+        return Err(EmailError::QuotedInvalid);
+    }
+
+    address.rsplit_once('@').ok_or(EmailError::MissingAt)
+}
+
+/// Returns whether the local part requires SMTPUTF8
+fn validate_local(local: &str) -> Result<bool, EmailError> {
+    if local.is_empty() {
+        return Err(EmailError::LocalEmpty);
+    }
+    if local.len() > MAX_LOCAL_LEN {
+        return Err(EmailError::LocalTooLong);
+    }
+
+    if let Some(inner) = local.strip_prefix('"') {
+        let inner = inner.strip_suffix('"').ok_or(EmailError::QuotedInvalid)?;
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                // quoted-pair
+                '\\' => match chars.next() {
+                    Some(e) if e == ' ' || e == '\t' || e.is_ascii_graphic() => {}
+                    _ => return Err(EmailError::QuotedInvalid),
+                },
+                '"' => return Err(EmailError::QuotedInvalid),
+                // qtext plus WSP
+                ' ' | '\t' => {}
+                c if c.is_ascii_graphic() => {}
+                c if !c.is_ascii() && !c.is_control() => {}
+                _ => return Err(EmailError::QuotedInvalid),
+            }
+        }
+        return Ok(!local.is_ascii());
+    }
+
+    // dot-atom
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err(EmailError::LocalDot);
+    }
+    if let Some(c) = local.chars().find(|c| !is_atext(*c) && *c != '.') {
+        return Err(EmailError::LocalInvalidChar(c));
+    }
+
+    Ok(!local.is_ascii())
+}
+
+/// RFC 5322 atext, extended with UTF8-non-ascii by RFC 6532
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+        || "!#$%&'*+-/=?^_`{|}~".contains(c)
+        || (!c.is_ascii() && !c.is_control() && !c.is_whitespace())
+}
+
+/// Returns the (unicode, ascii) forms of the domain
+fn validate_domain(domain: &str) -> Result<(String, String), EmailError> {
+    if domain.is_empty() {
+        return Err(EmailError::DomainEmpty);
+    }
+
+    if let Some(literal) = domain.strip_prefix('[') {
+        let literal = literal
+            .strip_suffix(']')
+            .ok_or_else(|| EmailError::IpLiteral(domain.to_string()))?;
+        let valid = match literal.get(..5) {
+            Some(tag) if tag.eq_ignore_ascii_case("IPv6:") => {
+                literal[5..].parse::<Ipv6Addr>().is_ok()
+            }
+            _ => literal.parse::<Ipv4Addr>().is_ok(),
+        };
+        if !valid {
+            return Err(EmailError::IpLiteral(domain.to_string()));
+        }
+        return Ok((domain.to_string(), domain.to_string()));
+    }
+
+    // UTS #46 mapping lowercases and punycode-encodes the domain
+    let ascii = idna::domain_to_ascii(domain).map_err(|_| EmailError::Idna(domain.to_string()))?;
+
+    if ascii.len() > MAX_DOMAIN_LEN {
+        return Err(EmailError::DomainTooLong);
+    }
+
+    for label in ascii.split('.') {
+        if label.len() > MAX_LABEL_LEN {
+            return Err(EmailError::DomainLabelTooLong(label.to_string()));
+        }
+        let ldh = label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if label.is_empty() || !ldh || label.starts_with('-') || label.ends_with('-') {
+            return Err(EmailError::DomainLabel(label.to_string()));
+        }
+    }
+
+    let (unicode, result) = idna::domain_to_unicode(&ascii);
+    if result.is_err() {
+        return Err(EmailError::Idna(domain.to_string()));
+    }
+
+    Ok((unicode, ascii))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_addresses() {
+        assert!(parse_email("jane.doe+tag@example.com").is_ok());
+        assert!(parse_email("\"john @ home\"@example.com").is_ok());
+        assert!(parse_email("user@[192.168.0.1]").is_ok());
+        assert!(parse_email("user@[IPv6:2001:db8::1]").is_ok());
+    }
+
+    #[test]
+    fn test_idn_and_eai() {
+        let email = parse_email("Jane@Bücher.EXAMPLE").unwrap();
+        assert_eq!(email.normalized(), "Jane@bücher.example");
+        assert_eq!(email.to_ascii(), "Jane@xn--bcher-kva.example");
+        assert!(!email.requires_smtputf8);
+
+        let email = parse_email("用户@例子.广告").unwrap();
+        assert!(email.requires_smtputf8);
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let code = |a: &str| parse_email(a).unwrap_err().code();
+        assert_eq!(code("jane.example.com"), "email-missing-at");
+        assert_eq!(code("@example.com"), "email-local-empty");
+        assert_eq!(code("jane..doe@example.com"), "email-local-dot");
+        assert_eq!(code("jane doe@example.com"), "email-local-invalid-char");
+        assert_eq!(code("\"unterminated@example.com"), "email-quoted-invalid");
+        assert_eq!(code("jane@"), "email-domain-empty");
+        assert_eq!(code("jane@-example.com"), "email-domain-label-invalid");
+        assert_eq!(code("jane@example..com"), "email-domain-label-invalid");
+        assert_eq!(code("jane@[300.1.1.1]"), "email-ip-literal-invalid");
+        assert_eq!(
+            code(&format!("{}@example.com", "a".repeat(65))),
+            "email-local-too-long"
+        );
+    }
+}
@@ -1,10 +1,14 @@
+use crate::phone::{format_phone, PhoneDisplayStyle};
 use crate::schema::YCard;
 use serde_yaml;
+use std::borrow::Cow;
 
 pub struct Formatter {
     indent_size: usize,
     phones_style: PhonesStyle,
     relocalize_keys: Option<String>,
+    phone_display: Option<(PhoneDisplayStyle, String)>,
+    name_locale: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +24,8 @@ impl Formatter {
             indent_size: 2,
             phones_style: PhonesStyle::Canonical,
             relocalize_keys: None,
+            phone_display: None,
+            name_locale: None,
         }
     }
 
@@ -33,15 +39,54 @@ impl Formatter {
         self
     }
 
+    /// Render phone numbers in human-readable display form for a locale
+    /// instead of E.164. The output is no longer canonical.
+    pub fn with_phone_display(mut self, style: PhoneDisplayStyle, locale: &str) -> Self {
+        self.phone_display = Some((style, locale.to_string()));
+        self
+    }
+
+    /// Put the form of the name that suits readers of `locale` first, with
+    /// the others as its alternates, and fill in its display name
+    pub fn with_name_locale(mut self, locale: &str) -> Self {
+        self.name_locale = Some(locale.to_string());
+        self
+    }
+
     /// Format yCard to canonical YAML
     pub fn format(&self, ycard: &YCard) -> Result<String, serde_yaml::Error> {
+        let mut ycard = Cow::Borrowed(ycard);
+        if let Some((style, locale)) = &self.phone_display {
+            self.display_phones(ycard.to_mut(), *style, locale);
+        }
+        if let Some(locale) = &self.name_locale {
+            let name = ycard.name.as_ref().map(|name| name.localized(locale));
+            if name != ycard.name {
+                let name = name.map(|mut name| {
+                    name.display_name = name.display();
+                    name
+                });
+                ycard.to_mut().name = name;
+            }
+        }
+
         // Convert to YAML with custom serialization
-        let yaml_str = serde_yaml::to_string(ycard)?;
+        let yaml_str = serde_yaml::to_string(&*ycard)?;
 
         // Post-process for formatting preferences
         Ok(self.post_process_yaml(yaml_str))
     }
 
+    fn display_phones(&self, ycard: &mut YCard, style: PhoneDisplayStyle, locale: &str) {
+        if let Some(phones) = &mut ycard.phones {
+            for phone in phones.iter_mut() {
+                // The extension is folded into the display string
+                phone.number = format_phone(phone, locale, style);
+                phone.ext = None;
+            }
+        }
+    }
+
     fn post_process_yaml(&self, mut yaml: String) -> String {
         // Apply formatting rules
         yaml = self.normalize_indentation(yaml);
@@ -106,4 +151,59 @@ mod tests {
         let result = formatter.format(&ycard);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_phone_display_option() {
+        use crate::generated_types::PhoneType;
+
+        let ycard = YCard {
+            phones: Some(vec![Phone {
+                number: "+33612345678".to_string(),
+                r#type: vec![PhoneType::Mobile],
+                ext: None,
+                preferred: None,
+                label: None,
+            }]),
+            ..YCard::default()
+        };
+
+        let formatter = Formatter::new().with_phone_display(PhoneDisplayStyle::Auto, "fr");
+        let result = formatter.format(&ycard).unwrap();
+        assert!(result.contains("06 12 34 56 78"));
+    }
+
+    #[test]
+    fn test_name_locale() {
+        let ycard = crate::parse(
+            "version: 1\nname:\n  displayName: 王小明\n  language: zh\n  alternates:\n    - {givenName: Xiaoming, familyName: Wang, language: zh-Latn}\n",
+            Some("zh"),
+        )
+        .unwrap();
+        let name = ycard.name.as_ref().unwrap();
+        assert_eq!(name.for_locale("zh-CN").display().unwrap(), "王小明");
+        assert_eq!(name.for_locale("en").display().unwrap(), "Wang Xiaoming");
+        assert_eq!(name.for_locale("ja").display().unwrap(), "王小明");
+
+        let formatted: YCard = serde_yaml::from_str(
+            &Formatter::new()
+                .with_name_locale("en-US")
+                .format(&ycard)
+                .unwrap(),
+        )
+        .unwrap();
+        let name = formatted.name.unwrap();
+        assert_eq!(name.display_name.as_deref(), Some("Wang Xiaoming"));
+        assert_eq!(
+            name.alternates.unwrap()[0].display_name.as_deref(),
+            Some("王小明")
+        );
+
+        // Without alternates the name is left as it is
+        let plain = crate::parse("version: 1\nname: Jane Doe\n", None).unwrap();
+        let formatter = Formatter::new().with_name_locale("ja");
+        assert_eq!(
+            formatter.format(&plain).unwrap(),
+            Formatter::new().format(&plain).unwrap()
+        );
+    }
 }
@@ -1,11 +1,34 @@
-use anyhow;
+use crate::alias_lint::lint_packs;
+use crate::locale::fallback_chain;
+use crate::text::fold_key;
+use crate::validator::Diagnostic;
+use anyhow::{self, bail};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Name of the pack generated from `schema.json`
+pub const BUNDLED_PACK: &str = "bundled";
+
+/// Major yCard schema version alias packs may target
+pub const SUPPORTED_SCHEMA_VERSION: u32 = 1;
 
 /// Internationalization alias data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AliasPack {
     pub version: String,
+    /// Name used to replace or remove the pack; generated when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Packs with higher priority win; equal priorities go to the later load
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    /// yCard schema version the aliases target, e.g. `1` or `1.0.0`
+    #[serde(
+        rename = "schemaVersion",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub schema_version: Option<String>,
     pub locales: HashMap<String, LocaleData>,
 }
 
@@ -17,12 +40,90 @@ pub struct LocaleData {
     pub type_aliases: HashMap<String, String>,
     pub countries: Option<HashMap<String, String>>,
     pub honorifics: Option<Vec<String>>,
+    /// Diagnostic message templates by message id, e.g.
+    /// `"phone-format": "Numéro non conforme à E.164 : {number}"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages: Option<HashMap<String, String>>,
+}
+
+/// Which alias table a lookup uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AliasKind {
+    /// `keyAliases`: input keys to canonical fields
+    Key,
+    /// `typeAliases`: phone, email and address types
+    Type,
+    /// `countries`: country names to ISO-3166-1 codes
+    Country,
+}
+
+impl AliasKind {
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s {
+            "key" => Some(Self::Key),
+            "type" => Some(Self::Type),
+            "country" => Some(Self::Country),
+            _ => None,
+        }
+    }
+
+    fn table(self, data: &LocaleData) -> Option<&HashMap<String, String>> {
+        match self {
+            Self::Key => Some(&data.key_aliases),
+            Self::Type => Some(&data.type_aliases),
+            Self::Country => data.countries.as_ref(),
+        }
+    }
+}
+
+/// A loaded pack as reported by [`AliasManager::packs`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackInfo {
+    pub name: String,
+    pub version: String,
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<String>,
+    /// Sorted locale tags the pack covers
+    pub locales: Vec<String>,
+}
+
+/// Where an alias lookup found its answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasResolution {
+    pub kind: AliasKind,
+    /// The word as looked up
+    pub input: String,
+    /// The field, type or country code it resolves to
+    pub target: String,
+    /// Name of the pack that supplied the alias
+    pub pack: String,
+    /// Locale in the fallback chain that held the alias
+    pub locale: String,
+    /// The chain that was searched, most specific first
+    pub chain: Vec<String>,
+}
+
+/// A pack with its lookup keys normalized, plus the pack as loaded
+#[derive(Clone)]
+struct LoadedPack {
+    name: String,
+    priority: i32,
+    pack: AliasPack,
+    /// Before normalization, for linting and introspection
+    source: AliasPack,
 }
 
 /// Global alias manager
 #[derive(Clone)]
 pub struct AliasManager {
-    packs: Vec<AliasPack>,
+    /// Lowest priority first; lookups walk the list backwards
+    packs: Vec<LoadedPack>,
+    /// Counter for names of packs loaded without one
+    unnamed: usize,
     default_locale: String,
 }
 
@@ -30,6 +131,7 @@ impl AliasManager {
     pub fn new() -> Self {
         let mut manager = Self {
             packs: Vec::new(),
+            unnamed: 0,
             default_locale: "en".to_string(),
         };
 
@@ -40,23 +142,131 @@ impl AliasManager {
 
     fn load_fallback_pack(&mut self) {
         let fallback_json = include_str!("../data/aliases.fallback.json");
-        if let Ok(pack) = serde_json::from_str::<AliasPack>(fallback_json) {
-            self.packs.push(pack);
+        if let Ok(mut pack) = serde_json::from_str::<AliasPack>(fallback_json) {
+            pack.name = Some(BUNDLED_PACK.to_string());
+            let _ = self.insert_pack(pack);
         }
     }
 
+    /// Load a pack, named and prioritized by its own `name` and `priority`.
+    /// A pack with the name of a loaded one replaces it.
     pub fn load_pack(&mut self, pack_json: &str) -> anyhow::Result<()> {
         let pack: AliasPack = serde_json::from_str(pack_json)?;
-        self.packs.push(pack);
-        Ok(())
+        self.insert_pack(pack).map(drop)
     }
 
     pub fn load_pack_bytes(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
         let pack: AliasPack = serde_json::from_slice(bytes)?;
-        self.packs.push(pack);
-        Ok(())
+        self.insert_pack(pack).map(drop)
+    }
+
+    /// Load a pack under `name` at `priority`, overriding what the pack
+    /// says, and replacing any loaded pack of that name
+    pub fn load_named_pack(
+        &mut self,
+        name: &str,
+        priority: i32,
+        pack_json: &str,
+    ) -> anyhow::Result<()> {
+        let mut pack: AliasPack = serde_json::from_str(pack_json)?;
+        pack.name = Some(name.to_string());
+        pack.priority = Some(priority);
+        self.insert_pack(pack).map(drop)
     }
 
+    /// Add `pack` above every pack of lower or equal priority and return
+    /// its name. Packs targeting another major schema version are rejected.
+    pub fn insert_pack(&mut self, pack: AliasPack) -> anyhow::Result<String> {
+        if let Some(version) = &pack.schema_version {
+            let major = version.split('.').next().unwrap_or_default();
+            if major.parse::<u32>().ok() != Some(SUPPORTED_SCHEMA_VERSION) {
+                bail!(
+                    "Alias pack targets schema version {}, but only version {} is supported",
+                    version,
+                    SUPPORTED_SCHEMA_VERSION
+                );
+            }
+        }
+
+        let name = match &pack.name {
+            Some(name) if name.trim().is_empty() => bail!("Alias pack name is empty"),
+            Some(name) => name.clone(),
+            None => loop {
+                self.unnamed += 1;
+                let name = format!("pack-{}", self.unnamed);
+                if !self.packs.iter().any(|p| p.name == name) {
+                    break name;
+                }
+            },
+        };
+        let priority = pack.priority.unwrap_or(0);
+
+        // Store lookup keys normalized the way input keys are, so `prénom`,
+        // `Prenom` and precomposed Hangul all match
+        let source = pack.clone();
+        let mut pack = pack;
+        let normalize = |table: HashMap<String, String>| {
+            table
+                .into_iter()
+                .map(|(k, v)| (normalize_key(&k), v))
+                .collect()
+        };
+        for data in pack.locales.values_mut() {
+            data.key_aliases = normalize(std::mem::take(&mut data.key_aliases));
+            data.type_aliases = normalize(std::mem::take(&mut data.type_aliases));
+            data.countries = data.countries.take().map(normalize);
+        }
+
+        self.packs.retain(|p| p.name != name);
+        let at = self.packs.partition_point(|p| p.priority <= priority);
+        self.packs.insert(
+            at,
+            LoadedPack {
+                name: name.clone(),
+                priority,
+                pack,
+                source,
+            },
+        );
+        Ok(name)
+    }
+
+    /// Unload the pack called `name`, returning it as loaded. Removing
+    /// [`BUNDLED_PACK`] leaves only custom packs until `clear_packs`.
+    pub fn remove_pack(&mut self, name: &str) -> Option<AliasPack> {
+        let index = self.packs.iter().position(|p| p.name == name)?;
+        Some(self.packs.remove(index).source)
+    }
+
+    /// Loaded packs, lowest priority first
+    pub fn packs(&self) -> Vec<PackInfo> {
+        self.packs
+            .iter()
+            .map(|loaded| {
+                let mut locales: Vec<String> = loaded.source.locales.keys().cloned().collect();
+                locales.sort();
+                PackInfo {
+                    name: loaded.name.clone(),
+                    version: loaded.source.version.clone(),
+                    priority: loaded.priority,
+                    schema_version: loaded.source.schema_version.clone(),
+                    locales,
+                }
+            })
+            .collect()
+    }
+
+    /// Every locale some loaded pack covers, sorted
+    pub fn locales(&self) -> Vec<String> {
+        let locales: BTreeSet<&String> = self
+            .packs
+            .iter()
+            .flat_map(|p| p.source.locales.keys())
+            .collect();
+        locales.into_iter().cloned().collect()
+    }
+
+    /// Unload every custom pack and restore the bundled one
     pub fn clear_packs(&mut self) {
         self.packs.clear();
         self.load_fallback_pack();
@@ -66,75 +276,187 @@ impl AliasManager {
         self.default_locale = locale.to_string();
     }
 
-    /// Resolve a key alias using BCP-47 fallback chain
-    pub fn resolve_key_alias(&self, key: &str, locale: Option<&str>) -> Option<String> {
-        let locale = locale.unwrap_or(&self.default_locale);
-        let locales = self.build_locale_chain(locale);
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// Locales whose key (or type) aliases contain `word`. Words known to
+    /// `root` are shared by every locale, so they yield no locales.
+    pub fn locales_knowing(&self, word: &str, is_type: bool) -> Vec<String> {
+        let word = self.normalize_key(word);
+        let knows = |data: &LocaleData| {
+            let table = if is_type {
+                &data.type_aliases
+            } else {
+                &data.key_aliases
+            };
+            table.contains_key(&word)
+        };
 
-        for pack in self.packs.iter().rev() {
-            for loc in &locales {
-                if let Some(locale_data) = pack.locales.get(loc) {
-                    if let Some(alias) = locale_data.key_aliases.get(&self.normalize_key(key)) {
-                        return Some(alias.clone());
+        let mut locales = Vec::new();
+        for loaded in &self.packs {
+            for (locale, data) in &loaded.pack.locales {
+                if knows(data) {
+                    if locale == "root" {
+                        return Vec::new();
+                    }
+                    if !locales.contains(locale) {
+                        locales.push(locale.clone());
                     }
                 }
             }
         }
-        None
+        locales
+    }
+
+    /// Key aliases of every locale in `locale`'s fallback chain, as
+    /// `(spelling, field)` pairs with spellings as written in their packs
+    pub fn key_alias_spellings(&self, locale: Option<&str>) -> Vec<(String, String)> {
+        let locale = locale.unwrap_or(&self.default_locale);
+        let chain = self.build_locale_chain(locale);
+        let mut seen = HashSet::new();
+        let mut spellings = Vec::new();
+        // Lookup order, so each alias keeps the field it resolves to
+        for loaded in self.packs.iter().rev() {
+            for loc in &chain {
+                if let Some(data) = loaded.source.locales.get(loc) {
+                    for (alias, field) in &data.key_aliases {
+                        if seen.insert(normalize_key(alias)) {
+                            spellings.push((alias.clone(), field.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        // Sorted so equally close suggestions come out the same every time
+        spellings.sort();
+        spellings
+    }
+
+    /// Whether some locale uses `key` for the `type` field
+    pub fn is_type_key(&self, key: &str) -> bool {
+        let key = self.normalize_key(key);
+        self.packs.iter().any(|loaded| {
+            loaded
+                .pack
+                .locales
+                .values()
+                .any(|data| data.key_aliases.get(&key).is_some_and(|k| k == "type"))
+        })
+    }
+
+    /// Resolve a key alias using BCP-47 fallback chain
+    pub fn resolve_key_alias(&self, key: &str, locale: Option<&str>) -> Option<String> {
+        self.explain(AliasKind::Key, key, locale).map(|r| r.target)
     }
 
     /// Resolve a type alias using BCP-47 fallback chain
     pub fn resolve_type_alias(&self, type_name: &str, locale: Option<&str>) -> Option<String> {
+        self.explain(AliasKind::Type, type_name, locale)
+            .map(|r| r.target)
+    }
+
+    /// ISO-3166-1 alpha-2 code for a country name in the locale's language
+    /// (`Allemagne` -> `DE` in French)
+    pub fn resolve_country(&self, name: &str, locale: Option<&str>) -> Option<String> {
+        self.explain(AliasKind::Country, name.trim(), locale)
+            .map(|r| r.target)
+    }
+
+    /// Resolve `word` like the `resolve_*` methods, reporting which pack and
+    /// locale supplied the answer. Higher-priority packs are searched first,
+    /// each along the whole fallback chain.
+    pub fn explain(
+        &self,
+        kind: AliasKind,
+        word: &str,
+        locale: Option<&str>,
+    ) -> Option<AliasResolution> {
         let locale = locale.unwrap_or(&self.default_locale);
-        let locales = self.build_locale_chain(locale);
-
-        for pack in self.packs.iter().rev() {
-            for loc in &locales {
-                if let Some(locale_data) = pack.locales.get(loc) {
-                    if let Some(alias) =
-                        locale_data.type_aliases.get(&self.normalize_key(type_name))
-                    {
-                        return Some(alias.clone());
-                    }
+        let chain = self.build_locale_chain(locale);
+        let key = self.normalize_key(word);
+
+        for loaded in self.packs.iter().rev() {
+            for loc in &chain {
+                let table = loaded.pack.locales.get(loc).and_then(|d| kind.table(d));
+                if let Some(target) = table.and_then(|t| t.get(&key)) {
+                    return Some(AliasResolution {
+                        kind,
+                        input: word.to_string(),
+                        target: target.clone(),
+                        pack: loaded.name.clone(),
+                        locale: loc.clone(),
+                        chain,
+                    });
                 }
             }
         }
         None
     }
 
-    /// Build BCP-47 fallback chain: fr-CA -> fr -> root
-    fn build_locale_chain(&self, locale: &str) -> Vec<String> {
-        let mut chain = vec![locale.to_string()];
+    /// Honorifics used in the locale's language, from the most specific
+    /// locale in the chain that lists any
+    pub fn honorifics(&self, locale: Option<&str>) -> Vec<String> {
+        let locale = locale.unwrap_or(&self.default_locale);
 
-        if let Some(lang) = locale.split('-').next() {
-            if lang != locale {
-                chain.push(lang.to_string());
+        for loc in &self.build_locale_chain(locale) {
+            for loaded in self.packs.iter().rev() {
+                if let Some(honorifics) = loaded
+                    .pack
+                    .locales
+                    .get(loc)
+                    .and_then(|d| d.honorifics.as_ref())
+                {
+                    return honorifics.clone();
+                }
             }
         }
+        Vec::new()
+    }
 
-        if locale != "root" {
-            chain.push("root".to_string());
+    /// Message template for diagnostic message `id` from the most specific
+    /// locale in the chain that has one
+    pub fn message_template(&self, id: &str, locale: Option<&str>) -> Option<&str> {
+        let locale = locale.unwrap_or(&self.default_locale);
+
+        for loc in &self.build_locale_chain(locale) {
+            for loaded in self.packs.iter().rev() {
+                let messages = loaded
+                    .pack
+                    .locales
+                    .get(loc)
+                    .and_then(|d| d.messages.as_ref());
+                if let Some(template) = messages.and_then(|m| m.get(id)) {
+                    return Some(template);
+                }
+            }
         }
+        None
+    }
 
-        chain
+    /// Build BCP-47 fallback chain: fr-CA -> fr -> root
+    fn build_locale_chain(&self, locale: &str) -> Vec<String> {
+        fallback_chain(locale)
     }
 
     /// Normalize key for case/diacritic insensitive matching
     fn normalize_key(&self, key: &str) -> String {
-        use unicode_normalization::UnicodeNormalization;
-
-        // Simple normalization: decompose and remove common diacritics
-        key.nfd()
-            .filter(|c| {
-                let c_u32 = *c as u32;
-                // Filter out combining diacritical marks
-                !(0x0300..=0x036F).contains(&c_u32)
-            })
-            .collect::<String>()
-            .to_lowercase()
+        normalize_key(key)
+    }
+
+    /// Conflicts and ambiguities in the loaded packs. Paths index into
+    /// [`packs`](Self::packs), lowest priority first.
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        let sources: Vec<AliasPack> = self.packs.iter().map(|p| p.source.clone()).collect();
+        lint_packs(&sources)
     }
 }
 
+/// Fold case and Latin diacritics the way alias lookups do
+pub(crate) fn normalize_key(key: &str) -> String {
+    fold_key(key)
+}
+
 impl Default for AliasManager {
     fn default() -> Self {
         Self::new()
@@ -144,12 +466,15 @@ impl Default for AliasManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::validator::DiagnosticLevel;
 
     #[test]
     fn test_locale_chain() {
         let manager = AliasManager::new();
         let chain = manager.build_locale_chain("fr-CA");
         assert_eq!(chain, vec!["fr-CA", "fr", "root"]);
+        let chain = manager.build_locale_chain("zh_tw");
+        assert_eq!(chain, vec!["zh-TW", "zh-Hant-TW", "zh-Hant", "root"]);
     }
 
     #[test]
@@ -157,5 +482,175 @@ mod tests {
         let manager = AliasManager::new();
         assert_eq!(manager.normalize_key("Téléphone"), "telephone");
         assert_eq!(manager.normalize_key("MÓVIL"), "movil");
+        // Hangul stays precomposed
+        assert_eq!(manager.normalize_key("전화"), "전화");
+    }
+
+    #[test]
+    fn test_bundled_locales() {
+        let manager = AliasManager::new();
+        let cases = [
+            (
+                "de",
+                "Vorname",
+                "name.givenName",
+                "geschäftlich",
+                "work",
+                "Österreich",
+                "AT",
+            ),
+            ("zh", "电话", "phones", "手机", "mobile", "美国", "US"),
+            (
+                "pt",
+                "endereço",
+                "addresses",
+                "celular",
+                "mobile",
+                "Brasil",
+                "BR",
+            ),
+            (
+                "it",
+                "cognome",
+                "name.familyName",
+                "ufficio",
+                "work",
+                "Svizzera",
+                "CH",
+            ),
+            ("ru", "почта", "emails", "домашний", "home", "Россия", "RU"),
+            ("ko", "주소", "addresses", "팩스", "fax", "대한민국", "KR"),
+            ("ar", "الهاتف", "phones", "المنزل", "home", "مصر", "EG"),
+            ("hi", "फ़ोन", "phones", "कार्यालय", "work", "भारत", "IN"),
+            ("th", "อีเมล", "emails", "มือถือ", "mobile", "ไทย", "TH"),
+            (
+                "vi",
+                "địa_chỉ",
+                "addresses",
+                "nhà",
+                "home",
+                "Việt Nam",
+                "VN",
+            ),
+            ("tr", "yönetici", "manager", "iş", "work", "Türkiye", "TR"),
+        ];
+        for (locale, key, field, type_name, canonical, country, code) in cases {
+            let locale = Some(locale);
+            assert_eq!(
+                manager.resolve_key_alias(key, locale).as_deref(),
+                Some(field)
+            );
+            assert_eq!(
+                manager.resolve_type_alias(type_name, locale).as_deref(),
+                Some(canonical)
+            );
+            assert_eq!(
+                manager.resolve_country(country, locale).as_deref(),
+                Some(code)
+            );
+            assert!(!manager.honorifics(locale).is_empty());
+        }
+        // Regional locales fall back to the language pack
+        assert_eq!(
+            manager
+                .resolve_key_alias("telemóvel", Some("pt-BR"))
+                .as_deref(),
+            Some("phones")
+        );
+        assert_eq!(manager.honorifics(Some("de-AT"))[0], "Herr");
+        // Taiwan uses the Traditional pack, which does not inherit from `zh`
+        assert_eq!(
+            manager.resolve_key_alias("電話", Some("zh-TW")).as_deref(),
+            Some("phones")
+        );
+        assert_eq!(manager.resolve_key_alias("电话", Some("zh-TW")), None);
+    }
+
+    #[test]
+    fn test_pack_layering() {
+        let mut manager = AliasManager::new();
+        let pack = |target: &str| {
+            format!(
+                r#"{{"version": "1", "locales": {{"fr": {{"keyAliases": {{"mél": "{}"}}, "typeAliases": {{}}}}}}}}"#,
+                target
+            )
+        };
+        manager
+            .load_named_pack("tenant", 10, &pack("emails"))
+            .unwrap();
+        manager
+            .load_named_pack("override", 0, &pack("phones"))
+            .unwrap();
+
+        // The higher priority wins over the later load
+        let resolution = manager
+            .explain(AliasKind::Key, "Mél", Some("fr-CA"))
+            .unwrap();
+        assert_eq!(resolution.target, "emails");
+        assert_eq!(resolution.pack, "tenant");
+        assert_eq!(resolution.locale, "fr");
+        assert_eq!(resolution.chain, vec!["fr-CA", "fr", "root"]);
+
+        let names: Vec<_> = manager.packs().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec![BUNDLED_PACK, "override", "tenant"]);
+        assert!(manager.locales().contains(&"zh-Hant".to_string()));
+
+        // Same name replaces; removal falls back to the next pack
+        manager
+            .load_named_pack("tenant", 10, &pack("urls"))
+            .unwrap();
+        assert_eq!(manager.packs().len(), 3);
+        assert_eq!(
+            manager.resolve_key_alias("mél", Some("fr")).as_deref(),
+            Some("urls")
+        );
+        assert!(manager.remove_pack("tenant").is_some());
+        assert!(manager.remove_pack("tenant").is_none());
+        assert_eq!(
+            manager.resolve_key_alias("mél", Some("fr")).as_deref(),
+            Some("phones")
+        );
+
+        // Unnamed packs get generated names; bundled aliases come from the bundled pack
+        manager.load_pack(&pack("emails")).unwrap();
+        assert_eq!(manager.packs()[2].name, "pack-1");
+        assert_eq!(
+            manager
+                .explain(AliasKind::Country, "Allemagne", Some("fr"))
+                .unwrap()
+                .pack,
+            BUNDLED_PACK
+        );
+
+        manager.clear_packs();
+        assert_eq!(manager.packs().len(), 1);
+    }
+
+    #[test]
+    fn test_pack_schema_version() {
+        let mut manager = AliasManager::new();
+        let pack = |version: &str| {
+            format!(
+                r#"{{"version": "1", "schemaVersion": "{}", "locales": {{}}}}"#,
+                version
+            )
+        };
+        assert!(manager.load_pack(&pack("1.2.0")).is_ok());
+        let error = manager.load_pack(&pack("2.0.0")).unwrap_err();
+        assert!(error.to_string().contains("schema version 2.0.0"));
+        assert!(manager.load_pack(&pack("next")).is_err());
+        assert_eq!(manager.packs().len(), 2);
+        assert_eq!(manager.packs()[0].schema_version.as_deref(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn test_bundled_pack_lints_clean() {
+        // Overlaps and redundant spellings are fine; conflicts are not
+        let findings: Vec<_> = AliasManager::new()
+            .lint()
+            .into_iter()
+            .filter(|d| matches!(d.level, DiagnosticLevel::Error | DiagnosticLevel::Warning))
+            .collect();
+        assert!(findings.is_empty(), "{:#?}", findings);
     }
 }
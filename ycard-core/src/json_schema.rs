@@ -0,0 +1,276 @@
+//! JSON Schema (Draft 2020-12) export of the canonical yCard model,
+//! built from the field definitions and enums generated from schema.json.
+
+use crate::generated_schema::{FieldDef, ENUM_VARIANTS, ROOT_FIELDS, TYPE_DEFS};
+use serde_json::{json, Map, Value};
+
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+pub const JSON_SCHEMA_ID: &str = "https://docs.yabl.dev/ycard/schema/v1/ycard.schema.json";
+
+/// Enums exported under `$defs`; the rest are internal to the tooling
+const EXPORTED_ENUMS: &[&str] = &["PhoneType", "EmailType", "AddressType", "Sex"];
+
+/// JSON Schema describing canonical yCard JSON as produced by the parser
+pub fn json_schema() -> Value {
+    let mut defs = Map::new();
+
+    for def in TYPE_DEFS {
+        defs.insert(
+            def.name.to_string(),
+            object_schema(Some(def.description), def.fields),
+        );
+    }
+
+    for (name, variants) in ENUM_VARIANTS {
+        if EXPORTED_ENUMS.contains(name) {
+            // Values outside the list are kept as custom types, so any string
+            // is valid; the known values are there for editors to suggest
+            defs.insert(
+                name.to_string(),
+                json!({ "type": "string", "examples": variants }),
+            );
+        }
+    }
+
+    let mut schema = object_schema(Some("yCard contact"), ROOT_FIELDS);
+    let root = schema.as_object_mut().expect("object schema");
+    root.insert("$schema".into(), json!(JSON_SCHEMA_DIALECT));
+    root.insert("$id".into(), json!(JSON_SCHEMA_ID));
+    root.insert("title".into(), json!("yCard"));
+    root.insert("$defs".into(), Value::Object(defs));
+    schema
+}
+
+fn object_schema(description: Option<&str>, fields: &[FieldDef]) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for field in fields {
+        let mut property = type_schema(field.ty);
+        // Unset optional fields serialize as null
+        if !field.required {
+            property = json!({ "anyOf": [property, { "type": "null" }] });
+        } else {
+            required.push(field.name);
+        }
+        property
+            .as_object_mut()
+            .expect("property schema")
+            .insert("description".into(), json!(field.description));
+        properties.insert(field.name.to_string(), property);
+    }
+
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    });
+    if let Some(description) = description {
+        schema["description"] = json!(description);
+    }
+    schema
+}
+
+fn type_schema(ty: &str) -> Value {
+    if let Some(item) = ty.strip_suffix("[]") {
+        return json!({ "type": "array", "items": type_schema(item) });
+    }
+    match ty {
+        "string" => json!({ "type": "string" }),
+        // The only numeric field is the integer schema version
+        "number" => json!({ "type": "integer", "minimum": 0 }),
+        "boolean" => json!({ "type": "boolean" }),
+        name => json!({ "$ref": format!("#/$defs/{}", name) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated_types::{AddressType, EmailType, PhoneType, Sex};
+    use crate::schema::*;
+
+    fn full_card() -> YCard {
+        let s = || Some("x".to_string());
+        let v = || Some(vec!["x".to_string()]);
+        let name = Name {
+            given_name: v(),
+            middle_name: v(),
+            family_name: v(),
+            honorific_prefix: v(),
+            honorific_suffix: v(),
+            display_name: s(),
+            script: s(),
+            phonetic_given_name: v(),
+            phonetic_family_name: v(),
+            phonetic_name: s(),
+            language: s(),
+            // An empty list ends the recursion
+            alternates: Some(vec![]),
+        };
+        YCard {
+            version: 1,
+            uid: s(),
+            name: Some(Name {
+                alternates: Some(vec![name.clone()]),
+                ..name
+            }),
+            nickname: v(),
+            pronouns: s(),
+            gender: Some(Gender {
+                sex: Some(Sex::Female),
+                identity: s(),
+            }),
+            phones: Some(vec![Phone {
+                number: "+15551234567".to_string(),
+                r#type: vec![PhoneType::Mobile],
+                ext: s(),
+                preferred: Some(true),
+                label: s(),
+            }]),
+            emails: Some(vec![Email {
+                address: "jane@example.com".to_string(),
+                r#type: vec![EmailType::Work],
+                preferred: Some(true),
+            }]),
+            addresses: Some(vec![Address {
+                r#type: vec![AddressType::Home],
+                formatted: s(),
+                components: Some(AddressComponents {
+                    street: s(),
+                    locality: s(),
+                    region: s(),
+                    postal_code: s(),
+                    country: s(),
+                }),
+            }]),
+            manager: s(),
+            languages: Some(vec![Language {
+                tag: "en".to_string(),
+                preferred: Some(true),
+            }]),
+            metadata: Some(Metadata {
+                locale: s(),
+                source: s(),
+            }),
+        }
+    }
+
+    /// The schema of a value: the non-null branch of an optional field,
+    /// with `$ref` followed
+    fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+        let schema = schema
+            .get("anyOf")
+            .and_then(Value::as_array)
+            .and_then(|any| any.iter().find(|s| s["type"] != "null"))
+            .unwrap_or(schema);
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => {
+                let name = reference.trim_start_matches("#/$defs/");
+                &root["$defs"][name]
+            }
+            None => schema,
+        }
+    }
+
+    /// Walk a fully populated card and its schema side by side, requiring
+    /// the same property names at every level
+    fn assert_matches(value: &Value, schema: &Value, root: &Value, path: &str) {
+        let schema = resolve(schema, root);
+        match value {
+            Value::Object(object) => {
+                let properties = schema["properties"]
+                    .as_object()
+                    .unwrap_or_else(|| panic!("{} is not an object in the schema", path));
+                let mut actual: Vec<_> = object.keys().collect();
+                let mut expected: Vec<_> = properties.keys().collect();
+                actual.sort();
+                expected.sort();
+                assert_eq!(actual, expected, "properties drifted at {}", path);
+                for (key, child) in object {
+                    assert_matches(child, &properties[key], root, &format!("{}/{}", path, key));
+                }
+            }
+            Value::Array(items) => {
+                assert_eq!(schema["type"], "array", "{} is not an array", path);
+                for item in items {
+                    assert_matches(item, &schema["items"], root, &format!("{}/0", path));
+                }
+            }
+            Value::String(s) => {
+                assert_eq!(schema["type"], "string", "{} is not a string", path);
+                // The full card only uses known enum values
+                if let Some(variants) = schema["examples"].as_array() {
+                    assert!(
+                        variants.contains(&json!(s)),
+                        "{} not a known value at {}",
+                        s,
+                        path
+                    );
+                }
+            }
+            Value::Number(_) => assert_eq!(schema["type"], "integer", "{}", path),
+            Value::Bool(_) => assert_eq!(schema["type"], "boolean", "{}", path),
+            Value::Null => panic!("{} should be populated", path),
+        }
+    }
+
+    #[test]
+    fn test_schema_matches_rust_types() {
+        let schema = json_schema();
+        let card = serde_json::to_value(full_card()).unwrap();
+        assert_matches(&card, &schema, &schema, "");
+    }
+
+    #[test]
+    fn test_enums_match_rust_types() {
+        let schema = json_schema();
+        let serialized = |v: Value| -> Vec<Value> { v.as_array().unwrap().clone() };
+
+        let phone_types = serde_json::to_value(vec![
+            PhoneType::Home,
+            PhoneType::Work,
+            PhoneType::Mobile,
+            PhoneType::Fax,
+            PhoneType::Pager,
+            PhoneType::Main,
+            PhoneType::Other,
+        ])
+        .unwrap();
+        assert_eq!(
+            serialized(phone_types),
+            serialized(schema["$defs"]["PhoneType"]["examples"].clone())
+        );
+
+        let other_types =
+            serde_json::to_value(vec![EmailType::Home, EmailType::Work, EmailType::Other]).unwrap();
+        assert_eq!(
+            serialized(other_types.clone()),
+            serialized(schema["$defs"]["EmailType"]["examples"].clone())
+        );
+        assert_eq!(
+            serialized(other_types),
+            serialized(schema["$defs"]["AddressType"]["examples"].clone())
+        );
+    }
+
+    #[test]
+    fn test_enums_accept_custom_values() {
+        let schema = json_schema();
+        for name in EXPORTED_ENUMS {
+            let def = &schema["$defs"][name];
+            assert_eq!(
+                def.as_object().unwrap().keys().collect::<Vec<_>>(),
+                ["examples", "type"],
+                "{} restricts its values",
+                name
+            );
+            assert_eq!(def["type"], "string", "{}", name);
+        }
+        assert_eq!(
+            serde_json::to_value(PhoneType::Custom("satellite".to_string())).unwrap(),
+            "satellite"
+        );
+    }
+}
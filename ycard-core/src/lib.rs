@@ -1,17 +1,53 @@
+pub mod address;
+pub mod alias_lint;
+pub mod collation;
+pub mod confusables;
+pub mod detect;
+pub mod email;
 pub mod formatter;
 pub mod generated_diagnostics;
+pub mod generated_schema;
 pub mod generated_types;
 pub mod i18n;
+pub mod json_schema;
+pub mod limits;
+pub mod locale;
+pub mod merge;
+pub mod messages;
 pub mod parser;
+pub mod phone;
+pub mod preference;
+pub mod registry;
+pub mod rules;
 pub mod schema;
+pub mod source_map;
+pub mod structure;
+pub mod text;
+pub mod typo;
 pub mod validator;
 pub mod wasm;
 
+pub use address::{normalize_postal_code, validate_components, AddressIssue};
+pub use collation::{sort_cards, Collator, SortKey};
+pub use confusables::{is_confusable, skeleton};
+pub use detect::{locale_from_env, locale_from_posix, LocaleGuess, LocaleSource};
+pub use email::{normalize_email, parse_email, EmailAddress, EmailError};
 pub use formatter::{Formatter, PhonesStyle};
-pub use i18n::{AliasManager, AliasPack, LocaleData};
+pub use i18n::{AliasKind, AliasManager, AliasPack, AliasResolution, LocaleData, PackInfo};
+pub use json_schema::json_schema;
+pub use limits::ParseOptions;
+pub use locale::{fallback_chain, LanguageId};
+pub use messages::localize;
 pub use parser::{ParseError, Parser};
+pub use phone::{format_phone, PhoneDisplayStyle};
+pub use preference::PreferenceInference;
+pub use registry::{canonicalize_language_tag, LanguageTagError};
+pub use rules::{builtin_rules, Rule};
 pub use schema::*;
-pub use validator::{Diagnostic, DiagnosticLevel, ValidationMode, Validator};
+pub use structure::validate_structure;
+pub use text::{fold_key, segment_words};
+pub use typo::{suggest_key, KeySuggestion};
+pub use validator::{locate, Diagnostic, DiagnosticLevel, MessageArgs, ValidationMode, Validator};
 
 // Re-export main functionality
 pub fn parse(input: &str, locale: Option<&str>) -> Result<YCard, ParseError> {
@@ -62,7 +98,7 @@ email: "jane@example.com"
         assert!(formatted.contains("version: 1"));
 
         // Validate
-        let diagnostics = validate(&ycard, ValidationMode::Lenient).unwrap();
+        let _diagnostics = validate(&ycard, ValidationMode::Lenient).unwrap();
         // Should have some warnings about normalization
     }
 
@@ -0,0 +1,318 @@
+//! Resource limits for parsing untrusted input.
+//!
+//! YAML is deserialized through a budget-tracking visitor so oversized
+//! scalars, deep nesting, huge collections and alias expansion
+//! ("billion laughs") are rejected while the document is being built,
+//! before they can exhaust memory.
+
+use crate::parser::ParseError;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use serde_yaml::value::{Tag, TaggedValue};
+use serde_yaml::{Mapping, Value};
+use std::cell::Cell;
+use std::fmt;
+
+/// Limits applied by the parser to each document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParseOptions {
+    /// Maximum size of the input text in bytes
+    pub max_input_bytes: usize,
+    /// Maximum nesting depth of sequences and mappings
+    pub max_depth: usize,
+    /// Maximum number of entries in a single sequence or mapping
+    pub max_collection_len: usize,
+    /// Maximum size of a single scalar in bytes
+    pub max_scalar_bytes: usize,
+    /// Maximum number of nodes in the document once YAML aliases are expanded
+    pub max_expanded_nodes: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_input_bytes: 1024 * 1024,
+            max_depth: 32,
+            max_collection_len: 1_000,
+            max_scalar_bytes: 64 * 1024,
+            max_expanded_nodes: 50_000,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Fail fast on input that is too large to be worth parsing
+    pub fn check_input_len(&self, len: usize) -> Result<(), ParseError> {
+        if len > self.max_input_bytes {
+            return Err(ParseError::InputTooLarge {
+                size: len,
+                limit: self.max_input_bytes,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Parse YAML into a generic value, enforcing `options`
+pub(crate) fn parse_yaml(input: &str, options: &ParseOptions) -> Result<Value, ParseError> {
+    options.check_input_len(input.len())?;
+
+    let budget = Budget {
+        options,
+        nodes: Cell::new(0),
+        violation: Cell::new(None),
+    };
+    let seed = LimitedValue {
+        budget: &budget,
+        depth: 0,
+    };
+
+    match seed.deserialize(serde_yaml::Deserializer::from_str(input)) {
+        Ok(value) => Ok(value),
+        Err(e) => Err(match budget.violation.take() {
+            Some(violation) => violation,
+            None => {
+                // serde_yaml's own guards fire for the same kinds of abuse
+                let message = e.to_string();
+                if message.starts_with("repetition limit exceeded") {
+                    ParseError::ExpansionLimitExceeded {
+                        limit: options.max_expanded_nodes,
+                    }
+                } else if message.starts_with("recursion limit exceeded") {
+                    ParseError::DepthLimitExceeded {
+                        limit: options.max_depth,
+                    }
+                } else {
+                    ParseError::Yaml(e)
+                }
+            }
+        }),
+    }
+}
+
+struct Budget<'a> {
+    options: &'a ParseOptions,
+    nodes: Cell<usize>,
+    violation: Cell<Option<ParseError>>,
+}
+
+impl Budget<'_> {
+    fn fail<E: de::Error>(&self, violation: ParseError) -> E {
+        let error = E::custom(&violation);
+        self.violation.set(Some(violation));
+        error
+    }
+
+    fn node<E: de::Error>(&self) -> Result<(), E> {
+        let nodes = self.nodes.get() + 1;
+        self.nodes.set(nodes);
+        if nodes > self.options.max_expanded_nodes {
+            return Err(self.fail(ParseError::ExpansionLimitExceeded {
+                limit: self.options.max_expanded_nodes,
+            }));
+        }
+        Ok(())
+    }
+
+    fn scalar<E: de::Error>(&self, len: usize) -> Result<(), E> {
+        self.node()?;
+        if len > self.options.max_scalar_bytes {
+            return Err(self.fail(ParseError::ScalarTooLarge {
+                size: len,
+                limit: self.options.max_scalar_bytes,
+            }));
+        }
+        Ok(())
+    }
+
+    fn entry<E: de::Error>(&self, len: usize) -> Result<(), E> {
+        if len > self.options.max_collection_len {
+            return Err(self.fail(ParseError::CollectionTooLarge {
+                limit: self.options.max_collection_len,
+            }));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct LimitedValue<'a, 'b> {
+    budget: &'a Budget<'b>,
+    depth: usize,
+}
+
+impl LimitedValue<'_, '_> {
+    fn nested<E: de::Error>(self) -> Result<Self, E> {
+        self.budget.node()?;
+        if self.depth >= self.budget.options.max_depth {
+            return Err(self.budget.fail(ParseError::DepthLimitExceeded {
+                limit: self.budget.options.max_depth,
+            }));
+        }
+        Ok(Self {
+            budget: self.budget,
+            depth: self.depth + 1,
+        })
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for LimitedValue<'_, '_> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for LimitedValue<'_, '_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any YAML value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        self.budget.node()?;
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        self.budget.node()?;
+        Ok(Value::Number(v.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        self.budget.node()?;
+        Ok(Value::Number(v.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        self.budget.node()?;
+        Ok(Value::Number(v.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        self.budget.scalar(v.len())?;
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        self.budget.scalar(v.len())?;
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        self.budget.node()?;
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let child = self.nested()?;
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element_seed(child)? {
+            items.push(item);
+            self.budget.entry(items.len())?;
+        }
+        Ok(Value::Sequence(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let child = self.nested()?;
+        let mut mapping = Mapping::new();
+        while let Some(key) = map.next_key_seed(child)? {
+            let value = map.next_value_seed(child)?;
+            mapping.insert(key, value);
+            self.budget.entry(mapping.len())?;
+        }
+        Ok(Value::Mapping(mapping))
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Value, A::Error> {
+        use de::VariantAccess;
+
+        let (tag, variant) = data.variant::<String>()?;
+        let value = variant.newtype_variant_seed(self.nested()?)?;
+        Ok(Value::Tagged(Box::new(TaggedValue {
+            tag: Tag::new(tag),
+            value,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str, options: ParseOptions) -> Result<Value, ParseError> {
+        parse_yaml(input, &options)
+    }
+
+    #[test]
+    fn test_regular_documents_pass() {
+        let value = parse(
+            "version: 1\nname: Jane\nphones:\n  - number: '+1555'\n    type: [mobile]\n",
+            ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(value["phones"][0]["type"][0], Value::from("mobile"));
+
+        let anchors = "base: &b {street: Main}\nhome: *b\nwork: *b\n";
+        assert!(parse(anchors, ParseOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_limits() {
+        let small = |f: fn(&mut ParseOptions)| {
+            let mut options = ParseOptions::default();
+            f(&mut options);
+            options
+        };
+
+        assert!(matches!(
+            parse("name: Jane", small(|o| o.max_input_bytes = 4)),
+            Err(ParseError::InputTooLarge { size: 10, limit: 4 })
+        ));
+        let args = parse("name: Jane", small(|o| o.max_input_bytes = 4))
+            .unwrap_err()
+            .to_diagnostic()
+            .message_args
+            .unwrap();
+        assert_eq!(args.id, "input-too-large");
+        assert_eq!(args.args["size"], "10");
+        assert!(matches!(
+            parse("a: [[[[1]]]]", small(|o| o.max_depth = 3)),
+            Err(ParseError::DepthLimitExceeded { limit: 3 })
+        ));
+        assert!(matches!(
+            parse("a: [1, 2, 3, 4]", small(|o| o.max_collection_len = 3)),
+            Err(ParseError::CollectionTooLarge { limit: 3 })
+        ));
+        assert!(matches!(
+            parse("a: abcdefgh", small(|o| o.max_scalar_bytes = 4)),
+            Err(ParseError::ScalarTooLarge { size: 8, limit: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_billion_laughs() {
+        let mut input = String::from("a0: &a0 [lol, lol, lol, lol, lol, lol, lol, lol, lol]\n");
+        for i in 1..10 {
+            let refs = vec![format!("*a{}", i - 1); 9].join(", ");
+            input.push_str(&format!("a{i}: &a{i} [{refs}]\n"));
+        }
+
+        assert!(matches!(
+            parse(&input, ParseOptions::default()),
+            Err(ParseError::ExpansionLimitExceeded { limit: 50_000 })
+        ));
+    }
+}
@@ -0,0 +1,329 @@
+//! BCP-47 locale fallback following CLDR: likely subtags fill in the script
+//! a region implies (`zh-TW` is written in `Hant`), and parent locales
+//! override plain truncation (`en-GB` falls back to `en-001`, and
+//! `zh-Hant` to `root` rather than the Simplified `zh`).
+
+use crate::registry::{canonicalize_language_tag, LanguageTagError};
+use std::fmt;
+
+/// Language, script, region and variants of a BCP-47 tag. Extensions and
+/// private-use subtags are not part of locale fallback and are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageId {
+    pub language: String,
+    pub script: Option<String>,
+    pub region: Option<String>,
+    pub variants: Vec<String>,
+}
+
+impl LanguageId {
+    /// Parse a tag in any casing, with `-` or `_` separators
+    pub fn parse(tag: &str) -> Result<Self, LanguageTagError> {
+        let canonical = canonicalize_language_tag(tag)?;
+        let mut subtags = canonical.split('-').peekable();
+        let mut language = subtags.next().unwrap_or_default().to_string();
+        if language == "x" {
+            return Err(LanguageTagError::Malformed(canonical));
+        }
+        // Extended language subtags name the language on their own (zh-yue)
+        while let Some(extlang) = subtags.next_if(|s| s.len() == 3 && is_alpha(s)) {
+            language = extlang.to_string();
+        }
+        let script = subtags
+            .next_if(|s| s.len() == 4 && is_alpha(s))
+            .map(str::to_string);
+        let region = subtags
+            .next_if(|s| (s.len() == 2 && is_alpha(s)) || (s.len() == 3 && is_digit(s)))
+            .map(str::to_string);
+        let variants = subtags
+            .take_while(|s| s.len() > 1)
+            .map(str::to_string)
+            .collect();
+        Ok(Self {
+            language,
+            script,
+            region,
+            variants,
+        })
+    }
+
+    /// Fill in the likely script and region (`zh-TW` -> `zh-Hant-TW`,
+    /// `sr` -> `sr-Cyrl-RS`). Unknown languages are returned unchanged.
+    pub fn maximize(&self) -> Self {
+        let mut id = self.clone();
+        if id.script.is_none() {
+            id.script = likely_script(&id.language, id.region.as_deref()).map(str::to_string);
+        }
+        if id.region.is_none() {
+            id.region = likely_region(&id.language, id.script.as_deref()).map(str::to_string);
+        }
+        id
+    }
+
+    /// The next locale to try, or `None` once only `root` is left
+    pub fn parent(&self) -> Option<Self> {
+        let mut id = self.clone();
+        if id.variants.pop().is_some() {
+            return Some(id);
+        }
+        if let Some(parent) = explicit_parent(self) {
+            return Self::parse(parent).ok();
+        }
+        if id.region.take().is_some() {
+            return Some(id);
+        }
+        // A script other than the language's usual one has no parent but root
+        let script = id.script.take()?;
+        (Some(script.as_str()) == likely_script(&id.language, None)).then_some(id)
+    }
+}
+
+impl fmt::Display for LanguageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language)?;
+        for subtag in self.script.iter().chain(&self.region).chain(&self.variants) {
+            write!(f, "-{}", subtag)?;
+        }
+        Ok(())
+    }
+}
+
+/// Locales to search for `tag`, most specific first and ending in `root`:
+/// `fr-CA` -> `[fr-CA, fr, root]`, `zh-TW` -> `[zh-TW, zh-Hant-TW, zh-Hant, root]`.
+/// Strings that are not BCP-47 tags are truncated at each separator.
+pub fn fallback_chain(tag: &str) -> Vec<String> {
+    let mut chain: Vec<String> = Vec::new();
+    let mut push = |locale: String| {
+        if !chain.contains(&locale) {
+            chain.push(locale);
+        }
+    };
+
+    match LanguageId::parse(tag) {
+        Ok(mut id) => {
+            // Keep the tag as written (with any extensions) first
+            push(canonicalize_language_tag(tag).unwrap_or_else(|_| id.to_string()));
+            push(id.to_string());
+            // A script implied by the region, when it is not the usual one
+            if id.script.is_none() {
+                let usual = likely_script(&id.language, None);
+                let implied = likely_script(&id.language, id.region.as_deref());
+                if implied.is_some() && implied != usual {
+                    id.script = implied.map(str::to_string);
+                    push(id.to_string());
+                }
+            }
+            while let Some(parent) = id.parent() {
+                push(parent.to_string());
+                id = parent;
+            }
+        }
+        _ => {
+            let tag = tag.trim().replace('_', "-");
+            let subtags: Vec<&str> = tag.split('-').filter(|s| !s.is_empty()).collect();
+            for end in (1..=subtags.len()).rev() {
+                push(subtags[..end].join("-"));
+            }
+        }
+    }
+    push("root".to_string());
+    chain
+}
+
+fn is_alpha(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_digit(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// CLDR likely subtags: (language, region or "", script)
+const LIKELY_SCRIPTS: &[(&str, &str, &str)] = &[
+    ("zh", "TW", "Hant"),
+    ("zh", "HK", "Hant"),
+    ("zh", "MO", "Hant"),
+    ("pa", "PK", "Arab"),
+    ("uz", "AF", "Arab"),
+    ("az", "IR", "Arab"),
+    ("mn", "CN", "Mong"),
+    ("sr", "ME", "Latn"),
+    ("ar", "", "Arab"),
+    ("az", "", "Latn"),
+    ("be", "", "Cyrl"),
+    ("bg", "", "Cyrl"),
+    ("bn", "", "Beng"),
+    ("el", "", "Grek"),
+    ("fa", "", "Arab"),
+    ("he", "", "Hebr"),
+    ("hi", "", "Deva"),
+    ("hy", "", "Armn"),
+    ("ja", "", "Jpan"),
+    ("ka", "", "Geor"),
+    ("kk", "", "Cyrl"),
+    ("km", "", "Khmr"),
+    ("ko", "", "Kore"),
+    ("mk", "", "Cyrl"),
+    ("mn", "", "Cyrl"),
+    ("mr", "", "Deva"),
+    ("ne", "", "Deva"),
+    ("pa", "", "Guru"),
+    ("ru", "", "Cyrl"),
+    ("sr", "", "Cyrl"),
+    ("ta", "", "Taml"),
+    ("th", "", "Thai"),
+    ("uk", "", "Cyrl"),
+    ("ur", "", "Arab"),
+    ("uz", "", "Latn"),
+    ("yue", "", "Hant"),
+    ("zh", "", "Hans"),
+];
+
+/// CLDR likely subtags: (language, script or "", region)
+const LIKELY_REGIONS: &[(&str, &str, &str)] = &[
+    ("zh", "Hant", "TW"),
+    ("sr", "Latn", "RS"),
+    ("pa", "Arab", "PK"),
+    ("uz", "Arab", "AF"),
+    ("az", "Arab", "IR"),
+    ("ar", "", "EG"),
+    ("de", "", "DE"),
+    ("en", "", "US"),
+    ("es", "", "ES"),
+    ("fr", "", "FR"),
+    ("hi", "", "IN"),
+    ("it", "", "IT"),
+    ("ja", "", "JP"),
+    ("ko", "", "KR"),
+    ("pl", "", "PL"),
+    ("pt", "", "BR"),
+    ("ru", "", "RU"),
+    ("sr", "", "RS"),
+    ("th", "", "TH"),
+    ("tr", "", "TR"),
+    ("vi", "", "VN"),
+    ("yue", "", "HK"),
+    ("zh", "", "CN"),
+];
+
+/// The script `language` is written in, in `region` when given. Languages
+/// missing from the table are assumed to use Latin.
+fn likely_script(language: &str, region: Option<&str>) -> Option<&'static str> {
+    let lookup = |region: &str| {
+        LIKELY_SCRIPTS
+            .iter()
+            .find(|(l, r, _)| *l == language && *r == region)
+            .map(|(_, _, script)| *script)
+    };
+    region
+        .and_then(lookup)
+        .or_else(|| lookup(""))
+        .or(crate::registry::is_language_code(language).then_some("Latn"))
+}
+
+fn likely_region(language: &str, script: Option<&str>) -> Option<&'static str> {
+    let lookup = |script: &str| {
+        LIKELY_REGIONS
+            .iter()
+            .find(|(l, s, _)| *l == language && *s == script)
+            .map(|(_, _, region)| *region)
+    };
+    script.and_then(lookup).or_else(|| lookup(""))
+}
+
+/// English outside the US, per CLDR `en-001`
+const EN_001: &[&str] = &[
+    "150", "AG", "AI", "AU", "BB", "BM", "BS", "BW", "BZ", "CA", "CC", "CK", "CM", "CX", "CY",
+    "DG", "DM", "ER", "FJ", "FK", "FM", "GB", "GD", "GG", "GH", "GI", "GM", "GY", "HK", "IE", "IL",
+    "IM", "IN", "IO", "JE", "JM", "KE", "KI", "KN", "KY", "LC", "LR", "LS", "MG", "MO", "MS", "MT",
+    "MU", "MV", "MW", "MY", "NA", "NF", "NG", "NR", "NU", "NZ", "PG", "PK", "PN", "PW", "RW", "SB",
+    "SC", "SD", "SG", "SH", "SL", "SS", "SX", "SZ", "TC", "TK", "TO", "TT", "TV", "TZ", "UG", "VC",
+    "VG", "VU", "WS", "ZA", "ZM", "ZW",
+];
+
+/// English in continental Europe, per CLDR `en-150`
+const EN_150: &[&str] = &["AT", "BE", "CH", "DE", "DK", "FI", "NL", "SE", "SI"];
+
+/// Latin American Spanish, per CLDR `es-419`
+const ES_419: &[&str] = &[
+    "AR", "BO", "BR", "BZ", "CL", "CO", "CR", "CU", "DO", "EC", "GT", "HN", "MX", "NI", "PA", "PE",
+    "PR", "PY", "SV", "US", "UY", "VE",
+];
+
+/// European Portuguese outside Portugal, per CLDR `pt-PT`
+const PT_PT: &[&str] = &[
+    "AO", "CH", "CV", "FR", "GQ", "GW", "LU", "MO", "MZ", "ST", "TL",
+];
+
+/// CLDR parent locales that differ from truncation
+fn explicit_parent(id: &LanguageId) -> Option<&'static str> {
+    let region = id.region.as_deref()?;
+    match (id.language.as_str(), id.script.as_deref()) {
+        ("en", None) if EN_150.contains(&region) => Some("en-150"),
+        ("en", None) if EN_001.contains(&region) => Some("en-001"),
+        ("es", None) if ES_419.contains(&region) => Some("es-419"),
+        ("pt", None) if PT_PT.contains(&region) => Some("pt-PT"),
+        ("zh", Some("Hant")) if region == "MO" => Some("zh-Hant-HK"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_chains() {
+        assert_eq!(fallback_chain("fr-CA"), ["fr-CA", "fr", "root"]);
+        assert_eq!(fallback_chain("fr_ca"), ["fr-CA", "fr", "root"]);
+        assert_eq!(
+            fallback_chain("zh-Hans-CN"),
+            ["zh-Hans-CN", "zh-Hans", "zh", "root"]
+        );
+        assert_eq!(
+            fallback_chain("zh-TW"),
+            ["zh-TW", "zh-Hant-TW", "zh-Hant", "root"]
+        );
+        assert_eq!(
+            fallback_chain("zh-MO"),
+            ["zh-MO", "zh-Hant-MO", "zh-Hant-HK", "zh-Hant", "root"]
+        );
+        assert_eq!(fallback_chain("en-GB"), ["en-GB", "en-001", "en", "root"]);
+        assert_eq!(
+            fallback_chain("en-DE"),
+            ["en-DE", "en-150", "en-001", "en", "root"]
+        );
+        assert_eq!(fallback_chain("es-MX"), ["es-MX", "es-419", "es", "root"]);
+        assert_eq!(fallback_chain("pt-AO"), ["pt-AO", "pt-PT", "pt", "root"]);
+        assert_eq!(
+            fallback_chain("sr-Latn-RS"),
+            ["sr-Latn-RS", "sr-Latn", "root"]
+        );
+        assert_eq!(
+            fallback_chain("de-CH-1996-u-co-phonebk"),
+            [
+                "de-CH-1996-u-co-phonebk",
+                "de-CH-1996",
+                "de-CH",
+                "de",
+                "root"
+            ]
+        );
+        assert_eq!(fallback_chain("root"), ["root"]);
+        assert_eq!(
+            fallback_chain("klingon-KX"),
+            ["klingon-KX", "klingon", "root"]
+        );
+    }
+
+    #[test]
+    fn test_likely_subtags() {
+        let maximize = |tag: &str| LanguageId::parse(tag).unwrap().maximize().to_string();
+        assert_eq!(maximize("zh-TW"), "zh-Hant-TW");
+        assert_eq!(maximize("zh"), "zh-Hans-CN");
+        assert_eq!(maximize("zh-Hant"), "zh-Hant-TW");
+        assert_eq!(maximize("sr"), "sr-Cyrl-RS");
+        assert_eq!(maximize("en"), "en-Latn-US");
+        assert_eq!(maximize("pt-PT"), "pt-Latn-PT");
+    }
+}
@@ -0,0 +1,305 @@
+//! Duplicate detection for phones, emails, addresses and languages
+//! gathered from several keys (`mobile:` plus `phones:`, two aliases of
+//! `emails`, …).
+//!
+//! Entries whose normalized values match are folded into the first one,
+//! combining their types and filling in fields the first one lacks.
+
+use crate::email::normalize_email;
+use crate::generated_types::{AddressType, EmailType, PhoneType};
+use crate::registry::canonicalize_language_tag;
+use crate::schema::{Address, AddressComponents, Email, Language, Phone};
+use crate::text::{is_unspaced_script, segment_words};
+use std::collections::HashMap;
+
+pub(crate) trait Merge: Sized {
+    /// Singular field name used in messages ("phone")
+    const KIND: &'static str;
+
+    /// Normalized identity; entries without one are never merged
+    fn merge_key(&self) -> Option<String>;
+
+    /// Short human-readable value for messages
+    fn describe(&self) -> String;
+
+    /// Fold a duplicate into `self`
+    fn absorb(&mut self, other: Self);
+}
+
+/// Merge keys of the entries read so far, each computed once
+pub(crate) struct MergeIndex(HashMap<String, usize>);
+
+impl MergeIndex {
+    pub(crate) fn new<T: Merge>(entries: &[T]) -> Self {
+        let mut keys = HashMap::new();
+        for (i, entry) in entries.iter().enumerate() {
+            if let Some(key) = entry.merge_key() {
+                keys.entry(key).or_insert(i);
+            }
+        }
+        Self(keys)
+    }
+}
+
+/// Add `entry` to `entries`, merging it into an existing duplicate.
+/// `index` must hold the keys of `entries`. Returns the entry it was merged into.
+pub(crate) fn merge_entry<'a, T: Merge>(
+    entries: &'a mut Vec<T>,
+    index: &mut MergeIndex,
+    entry: T,
+) -> Option<&'a T> {
+    let Some(key) = entry.merge_key() else {
+        entries.push(entry);
+        return None;
+    };
+    match index.0.get(&key) {
+        // Absorbing never changes the key fields of an entry
+        Some(&i) => {
+            entries[i].absorb(entry);
+            Some(&entries[i])
+        }
+        None => {
+            index.0.insert(key, entries.len());
+            entries.push(entry);
+            None
+        }
+    }
+}
+
+/// Union of two type lists; `other` is dropped once a specific type is known
+fn union_types<T: PartialEq>(types: &mut Vec<T>, more: Vec<T>, other: T) {
+    for t in more {
+        if !types.contains(&t) {
+            types.push(t);
+        }
+    }
+    if types.len() > 1 {
+        types.retain(|t| *t != other);
+    }
+}
+
+fn either_preferred(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Case- and whitespace-insensitive form of free text. Text in scripts
+/// written without spaces is compared word by word, so `東京都 千代田区`
+/// matches `東京都千代田区`.
+fn fold_text(s: &str) -> String {
+    if is_unspaced_script(s) {
+        let unspaced: String = s.split_whitespace().collect();
+        return segment_words(&unspaced).join(" ").to_lowercase();
+    }
+    s.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl Merge for Phone {
+    const KIND: &'static str = "phone";
+
+    fn merge_key(&self) -> Option<String> {
+        if self.number.is_empty() {
+            return None;
+        }
+        Some(match &self.ext {
+            Some(ext) => format!("{};ext={}", self.number, ext.trim()),
+            None => self.number.clone(),
+        })
+    }
+
+    fn describe(&self) -> String {
+        self.number.clone()
+    }
+
+    fn absorb(&mut self, other: Self) {
+        union_types(&mut self.r#type, other.r#type, PhoneType::Other);
+        self.preferred = either_preferred(self.preferred, other.preferred);
+        if self.label.is_none() {
+            self.label = other.label;
+        }
+    }
+}
+
+impl Merge for Email {
+    const KIND: &'static str = "email";
+
+    fn merge_key(&self) -> Option<String> {
+        let address = self.address.trim();
+        if address.is_empty() {
+            return None;
+        }
+        // Invalid addresses are still merged when identical ignoring case
+        Some(normalize_email(address).unwrap_or_else(|_| address.to_lowercase()))
+    }
+
+    fn describe(&self) -> String {
+        self.address.clone()
+    }
+
+    fn absorb(&mut self, other: Self) {
+        union_types(&mut self.r#type, other.r#type, EmailType::Other);
+        self.preferred = either_preferred(self.preferred, other.preferred);
+    }
+}
+
+impl Merge for Address {
+    const KIND: &'static str = "address";
+
+    fn merge_key(&self) -> Option<String> {
+        let components = self.components.as_ref().map(|c| {
+            [
+                &c.street,
+                &c.locality,
+                &c.region,
+                &c.postal_code,
+                &c.country,
+            ]
+            .iter()
+            .map(|part| part.as_deref().map(fold_text).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("|")
+        });
+        let formatted = self.formatted.as_deref().map(fold_text);
+        match (formatted, components) {
+            (None, None) => None,
+            (formatted, components) => Some(format!(
+                "{}#{}",
+                formatted.unwrap_or_default(),
+                components.unwrap_or_default()
+            )),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match (&self.formatted, &self.components) {
+            (Some(formatted), _) => formatted.clone(),
+            (
+                None,
+                Some(AddressComponents {
+                    street, locality, ..
+                }),
+            ) => [street, locality]
+                .iter()
+                .filter_map(|part| part.as_deref())
+                .collect::<Vec<_>>()
+                .join(", "),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn absorb(&mut self, other: Self) {
+        union_types(&mut self.r#type, other.r#type, AddressType::Other);
+    }
+}
+
+impl Merge for Language {
+    const KIND: &'static str = "language";
+
+    fn merge_key(&self) -> Option<String> {
+        let tag = self.tag.trim();
+        if tag.is_empty() {
+            return None;
+        }
+        // `en_US` and `en-us` are the same language
+        Some(canonicalize_language_tag(tag).unwrap_or_else(|_| tag.to_lowercase()))
+    }
+
+    fn describe(&self) -> String {
+        self.tag.clone()
+    }
+
+    fn absorb(&mut self, other: Self) {
+        self.preferred = either_preferred(self.preferred, other.preferred);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phone(number: &str, types: Vec<PhoneType>) -> Phone {
+        Phone {
+            number: number.to_string(),
+            r#type: types,
+            ext: None,
+            preferred: None,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_phones() {
+        let mut phones = vec![phone("+15551234567", vec![PhoneType::Mobile])];
+        let mut index = MergeIndex::new(&phones);
+
+        let mut work = phone("+15551234567", vec![PhoneType::Work]);
+        work.preferred = Some(true);
+        let merged = merge_entry(&mut phones, &mut index, work).unwrap();
+        assert_eq!(merged.r#type, vec![PhoneType::Mobile, PhoneType::Work]);
+        assert_eq!(merged.preferred, Some(true));
+
+        let other = phone("+15551234567", vec![PhoneType::Other]);
+        assert!(merge_entry(&mut phones, &mut index, other).is_some());
+        assert_eq!(phones[0].r#type, vec![PhoneType::Mobile, PhoneType::Work]);
+
+        let mut extension = phone("+15551234567", vec![PhoneType::Work]);
+        extension.ext = Some("12".to_string());
+        assert!(merge_entry(&mut phones, &mut index, extension).is_none());
+        assert_eq!(phones.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_emails_and_addresses() {
+        let email = |address: &str, t: EmailType| Email {
+            address: address.to_string(),
+            r#type: vec![t],
+            preferred: None,
+        };
+        let mut emails = vec![email("Jane@Example.com", EmailType::Other)];
+        let mut index = MergeIndex::new(&emails);
+        assert!(merge_entry(
+            &mut emails,
+            &mut index,
+            email("Jane@EXAMPLE.com", EmailType::Work)
+        )
+        .is_some());
+        // Local parts are case-sensitive
+        assert!(merge_entry(
+            &mut emails,
+            &mut index,
+            email("jane@example.com", EmailType::Home)
+        )
+        .is_none());
+        assert_eq!(emails[0].r#type, vec![EmailType::Work]);
+
+        let address = |formatted: &str| Address {
+            r#type: vec![AddressType::Home],
+            formatted: Some(formatted.to_string()),
+            components: None,
+        };
+        let mut addresses = vec![address("1 Main St,  Springfield")];
+        let mut index = MergeIndex::new(&addresses);
+        assert!(merge_entry(
+            &mut addresses,
+            &mut index,
+            address("1 main st, springfield")
+        )
+        .is_some());
+        assert_eq!(addresses.len(), 1);
+
+        // Spaces between words don't matter in unspaced scripts
+        let mut addresses = vec![address("東京都千代田区丸の内")];
+        let mut index = MergeIndex::new(&addresses);
+        assert!(merge_entry(
+            &mut addresses,
+            &mut index,
+            address("東京都 千代田区 丸の内")
+        )
+        .is_some());
+    }
+}
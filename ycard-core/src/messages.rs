@@ -0,0 +1,190 @@
+//! Diagnostic messages in the reader's language.
+//!
+//! Diagnostics that can be localized carry [`MessageArgs`]: a message id
+//! (usually the diagnostic code) and named parameters. Alias packs supply
+//! templates per locale under `messages`, with `{name}` placeholders and
+//! `{{`/`}}` for literal braces. The English message built by the
+//! diagnostic's producer is kept when no template is found or a template
+//! names a parameter the diagnostic lacks.
+
+use crate::i18n::AliasManager;
+use crate::validator::{Diagnostic, MessageArgs};
+use std::collections::BTreeMap;
+
+/// Fill `{name}` placeholders in `template` from `args`. Returns `None`
+/// for a placeholder without a value or an unbalanced brace.
+pub fn render(template: &str, args: &BTreeMap<String, String>) -> Option<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        c => name.push(c),
+                    }
+                }
+                out.push_str(args.get(name.trim())?);
+            }
+            '}' => return None,
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+/// The message of `args` in `locale`, if a loaded catalog has it
+pub fn localized_message(
+    args: &MessageArgs,
+    aliases: &AliasManager,
+    locale: Option<&str>,
+) -> Option<String> {
+    render(aliases.message_template(&args.id, locale)?, &args.args)
+}
+
+/// Rewrite the messages of `diagnostics` in `locale`, or in the alias
+/// manager's default locale
+pub fn localize(diagnostics: &mut [Diagnostic], aliases: &AliasManager, locale: Option<&str>) {
+    for diagnostic in diagnostics {
+        let Some(args) = &diagnostic.message_args else {
+            continue;
+        };
+        if let Some(message) = localized_message(args, aliases, locale) {
+            diagnostic.message = message;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let args: BTreeMap<String, String> = [("number".to_string(), "555".to_string())].into();
+        assert_eq!(
+            render("Numéro non E.164 : {number}", &args).as_deref(),
+            Some("Numéro non E.164 : 555")
+        );
+        assert_eq!(
+            render("{{literal}} { number }", &args).as_deref(),
+            Some("{literal} 555")
+        );
+        assert_eq!(render("{missing}", &args), None);
+        assert_eq!(render("{number", &args), None);
+        assert_eq!(render("number}", &args), None);
+    }
+
+    #[test]
+    fn test_localize() {
+        use crate::validator::{ValidationMode, Validator};
+
+        let ycard = crate::parse(
+            "version: 1\nemails:\n  - address: a@example.com\n    preferred: true\n  - address: b@example.com\n    preferred: true\n",
+            None,
+        )
+        .unwrap();
+        let diagnostics = Validator::new(ValidationMode::Lenient)
+            .validate(&ycard)
+            .unwrap();
+        let english = diagnostics[0].message.clone();
+
+        let mut manager = AliasManager::new();
+        let localized = |manager: &AliasManager, locale| {
+            let mut diagnostics = diagnostics.clone();
+            localize(&mut diagnostics, manager, locale);
+            diagnostics[0].message.clone()
+        };
+
+        // fr-CA falls back to the bundled French catalog
+        assert_eq!(
+            localized(&manager, Some("fr-CA")),
+            "2 emails sont marqués préférés (a@example.com, b@example.com) ; seul le premier compte"
+        );
+        assert_eq!(localized(&manager, Some("sw")), english);
+
+        // A pack's catalog wins over the bundled one; a template naming an
+        // unknown parameter leaves the English message
+        manager
+            .load_named_pack(
+                "tenant",
+                10,
+                r#"{"version": "1", "locales": {
+                    "fr-CA": {"keyAliases": {}, "typeAliases": {}, "messages": {
+                        "preferred-multiple": "Trop de {field} préférés : {values}"}},
+                    "sw": {"keyAliases": {}, "typeAliases": {}, "messages": {
+                        "preferred-multiple": "{haipo}"}}}}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            localized(&manager, Some("fr-CA")),
+            "Trop de emails préférés : a@example.com, b@example.com"
+        );
+        assert_eq!(localized(&manager, Some("sw")), english);
+    }
+
+    #[test]
+    fn test_localize_check_output() {
+        use crate::validator::{locate, ValidationMode, Validator};
+
+        // The same steps as `ycard check --locale fr`
+        let source = "version: 1\nemails:\n  - alice.example.com\n";
+        let manager = AliasManager::new();
+        let parser = crate::Parser::with_alias_manager(manager.clone());
+        let (ycard, parse_diagnostics) = parser.parse_partial(source, Some("fr"));
+        let mut diagnostics = Validator::new(ValidationMode::Lenient)
+            .validate(&ycard)
+            .unwrap();
+        locate(&mut diagnostics, &ycard, source);
+        diagnostics.extend(parse_diagnostics);
+        localize(&mut diagnostics, &manager, Some("fr"));
+
+        let missing_at = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("email-missing-at"))
+            .unwrap();
+        assert_eq!(
+            missing_at.message,
+            "Adresse e-mail invalide alice.example.com : séparateur « @ » manquant"
+        );
+        let expanded = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("shorthand-expanded"))
+            .unwrap();
+        assert_eq!(
+            expanded.message,
+            "Forme abrégée /emails/0 développée en forme canonique"
+        );
+    }
+
+    #[test]
+    fn test_bundled_catalogs_cover_codes() {
+        use crate::generated_diagnostics::DIAGNOSTIC_CODES;
+
+        // YAML syntax errors and alias pack lints are reported in English only
+        let manager = AliasManager::new();
+        for code in DIAGNOSTIC_CODES {
+            if code.code == "yaml-syntax-error" || code.code.starts_with("alias-") {
+                continue;
+            }
+            for locale in ["fr", "es", "ja"] {
+                assert!(
+                    manager.message_template(code.code, Some(locale)).is_some(),
+                    "no {} template for {}",
+                    locale,
+                    code.code
+                );
+            }
+        }
+    }
+}
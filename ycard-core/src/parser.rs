@@ -1,283 +1,1479 @@
-use crate::generated_types::{EmailType, PhoneType, PHONE_SHORTHAND_KEYS};
+use crate::confusables;
+use crate::detect::{detect_locale, LocaleGuess, LocaleSource};
+use crate::generated_schema::{ROOT_FIELDS, TYPE_DEFS};
+use crate::generated_types::{
+    AddressType, EmailType, PhoneType, Sex, ADDRESS_SHORTHANDS, EMAIL_SHORTHANDS, PHONE_SHORTHANDS,
+};
 use crate::i18n::AliasManager;
+use crate::limits::{parse_yaml, ParseOptions};
+use crate::merge::{merge_entry, Merge, MergeIndex};
+use crate::preference::PreferenceInference;
 use crate::schema::*;
+use crate::source_map::{self, path_pointer, PathSegment, SourceMap};
+use crate::text::{is_unspaced_script, split_name};
+use crate::typo::{suggest_key, KeySuggestion};
+use crate::validator::{
+    CodeFix, Diagnostic, DiagnosticLevel, MessageArgs, Position, Range, TextEdit,
+};
 use serde_yaml::Value;
+use std::cell::OnceCell;
+use std::collections::HashSet;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ParseError {
     #[error("YAML parsing error: {0}")]
     Yaml(#[from] serde_yaml::Error),
-    #[error("Invalid schema: {0}")]
-    Schema(String),
-    #[error("Phone normalization error: {0}")]
-    Phone(String),
-    #[error("Email validation error: {0}")]
-    Email(String),
+    #[error("Invalid schema: {message}")]
+    Schema {
+        message: String,
+        range: Option<Range>,
+    },
+    #[error("Phone normalization error: {message}")]
+    Phone {
+        message: String,
+        range: Option<Range>,
+    },
+    #[error("Email validation error: {message}")]
+    Email {
+        message: String,
+        range: Option<Range>,
+    },
+    #[error("Input is {size} bytes, exceeding the limit of {limit}")]
+    InputTooLarge { size: usize, limit: usize },
+    #[error("Nesting exceeds the maximum depth of {limit}")]
+    DepthLimitExceeded { limit: usize },
+    #[error("Sequence or mapping exceeds {limit} entries")]
+    CollectionTooLarge { limit: usize },
+    #[error("Scalar is {size} bytes, exceeding the limit of {limit}")]
+    ScalarTooLarge { size: usize, limit: usize },
+    #[error("Alias expansion exceeds {limit} nodes")]
+    ExpansionLimitExceeded { limit: usize },
 }
 
+impl ParseError {
+    /// Stable diagnostic code for this failure
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::Yaml(_) => "yaml-syntax-error",
+            ParseError::Schema { .. } => "field-invalid",
+            ParseError::Phone { .. } => "phone-invalid",
+            ParseError::Email { .. } => "email-invalid",
+            ParseError::InputTooLarge { .. } => "input-too-large",
+            ParseError::DepthLimitExceeded { .. } => "depth-limit-exceeded",
+            ParseError::CollectionTooLarge { .. } => "collection-too-large",
+            ParseError::ScalarTooLarge { .. } => "scalar-too-large",
+            ParseError::ExpansionLimitExceeded { .. } => "expansion-limit-exceeded",
+        }
+    }
+
+    /// Location of the failure in the input, when known. YAML syntax errors
+    /// cover the single character serde_yaml stopped at.
+    pub fn range(&self) -> Option<Range> {
+        match self {
+            ParseError::Yaml(e) => e.location().map(|location| {
+                let start = Position {
+                    line: location.line().saturating_sub(1) as u32,
+                    character: location.column().saturating_sub(1) as u32,
+                };
+                Range {
+                    end: Position {
+                        line: start.line,
+                        character: start.character + 1,
+                    },
+                    start,
+                }
+            }),
+            ParseError::Schema { range, .. }
+            | ParseError::Phone { range, .. }
+            | ParseError::Email { range, .. } => range.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let message = match self {
+            // serde_yaml appends the location, which the range already carries
+            ParseError::Yaml(e) => {
+                let message = e.to_string();
+                match message.find(" at line ") {
+                    Some(pos) => format!("YAML parsing error: {}", &message[..pos]),
+                    None => self.to_string(),
+                }
+            }
+            _ => self.to_string(),
+        };
+        Diagnostic {
+            level: DiagnosticLevel::Error,
+            message,
+            code: Some(self.code().to_string()),
+            range: self.range(),
+            fixes: vec![],
+            path: None,
+            message_args: self.message_args(),
+        }
+    }
+
+    /// Message arguments of a field or resource limit failure
+    fn message_args(&self) -> Option<MessageArgs> {
+        let args = match self {
+            ParseError::Schema { message, .. }
+            | ParseError::Phone { message, .. }
+            | ParseError::Email { message, .. } => vec![("error", message.clone())],
+            ParseError::InputTooLarge { size, limit }
+            | ParseError::ScalarTooLarge { size, limit } => {
+                vec![("size", size.to_string()), ("limit", limit.to_string())]
+            }
+            ParseError::DepthLimitExceeded { limit }
+            | ParseError::CollectionTooLarge { limit }
+            | ParseError::ExpansionLimitExceeded { limit } => vec![("limit", limit.to_string())],
+            _ => return None,
+        };
+        Some(MessageArgs::new(self.code(), args))
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        error.to_diagnostic()
+    }
+}
+
+type Path = [PathSegment];
+
+/// Shorter prefixes tried by `parse_partial` after a YAML syntax error
+const MAX_RECOVERY_ATTEMPTS: usize = 8;
+
+fn child(path: &Path, segment: impl Into<PathSegment>) -> Vec<PathSegment> {
+    let mut path = path.to_vec();
+    path.push(segment.into());
+    path
+}
+
+/// Which part of a located node a diagnostic points at
+#[derive(Clone, Copy)]
+enum Span {
+    Key,
+    Value,
+    Full,
+}
+
+/// Records how lenient parsing interpreted the input. Plain `parse_lenient`
+/// collects nothing, and the source is only scanned once a location is needed.
+struct Report<'a> {
+    input: &'a str,
+    source: OnceCell<SourceMap>,
+    collect: bool,
+    /// Turn field errors into diagnostics and keep going
+    recover: bool,
+    /// Fields written under more than one top-level key, where expanding a
+    /// shorthand into its own block would repeat the field's key
+    shared_fields: HashSet<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Report<'a> {
+    fn disabled(input: &'a str) -> Self {
+        Self {
+            input,
+            source: OnceCell::new(),
+            collect: false,
+            recover: false,
+            shared_fields: HashSet::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn collecting(input: &'a str, recover: bool) -> Self {
+        Self {
+            collect: true,
+            recover,
+            ..Self::disabled(input)
+        }
+    }
+
+    fn range(&self, path: &Path, span: Span) -> Option<Range> {
+        let entry = self
+            .source
+            .get_or_init(|| SourceMap::new(self.input))
+            .get(path)?;
+        Some(match span {
+            Span::Key => entry.key.clone(),
+            Span::Value => entry.value.clone(),
+            Span::Full => entry.full.clone(),
+        })
+    }
+
+    fn schema_error(&self, message: &str, path: &Path) -> ParseError {
+        ParseError::Schema {
+            message: message.to_string(),
+            range: self.range(path, Span::Value),
+        }
+    }
+
+    fn phone_error(&self, message: String, path: &Path) -> ParseError {
+        ParseError::Phone {
+            message,
+            range: self.range(path, Span::Value),
+        }
+    }
+
+    /// In recovering mode, record a failed field as a diagnostic instead of failing
+    fn recover<T>(&mut self, result: Result<T, ParseError>) -> Result<Option<T>, ParseError> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if self.recover => {
+                self.diagnostics.push(error.to_diagnostic());
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn push(
+        &mut self,
+        level: DiagnosticLevel,
+        code: &str,
+        message: String,
+        path: &Path,
+        span: Span,
+        fix: Option<(String, Span, String)>,
+    ) -> Option<&mut Diagnostic> {
+        if !self.collect {
+            return None;
+        }
+        let range = self.range(path, span);
+        let fixes = fix
+            .and_then(|(title, span, new_text)| {
+                let range = self.range(path, span)?;
+                Some(CodeFix {
+                    title,
+                    kind: "quickfix".to_string(),
+                    edit: Some(TextEdit { range, new_text }),
+                    path: None,
+                    value: None,
+                })
+            })
+            .into_iter()
+            .collect();
+        self.diagnostics.push(Diagnostic {
+            level,
+            message,
+            code: Some(code.to_string()),
+            range,
+            fixes,
+            path: None,
+            message_args: None,
+        });
+        self.diagnostics.last_mut()
+    }
+
+    fn alias(&mut self, written: &str, canonical: &str, path: &Path, span: Span) {
+        if let Some(d) = self.push(
+            DiagnosticLevel::Info,
+            "alias-resolved",
+            format!("\"{}\" interpreted as \"{}\"", written, canonical),
+            path,
+            span,
+            Some((format!("Use {}", canonical), span, canonical.to_string())),
+        ) {
+            d.message_args = Some(MessageArgs::new(
+                "alias-resolved",
+                [
+                    ("written", written.to_string()),
+                    ("canonical", canonical.to_string()),
+                ],
+            ));
+        }
+    }
+
+    /// Localize `d` with the written path of its value as the only argument
+    fn at_path(d: Option<&mut Diagnostic>, path: &Path) {
+        if let Some(d) = d {
+            let id = d.code.clone().unwrap_or_default();
+            d.message_args = Some(MessageArgs::new(&id, [("path", path_pointer(path))]));
+        }
+    }
+
+    fn coerced(&mut self, message: String, path: &Path, new_text: Option<String>) {
+        let fix = new_text.map(|text| ("Use canonical form".to_string(), Span::Value, text));
+        let d = self.push(
+            DiagnosticLevel::Info,
+            "type-coerced",
+            message,
+            path,
+            Span::Value,
+            fix,
+        );
+        Self::at_path(d, path);
+    }
+
+    /// A single value read as a one-element list. The fix writes the list:
+    /// an inline value goes in brackets and a nested block becomes a `- `
+    /// item. Block scalars (`|`, `>`) get no fix, as brackets cannot hold them.
+    fn single_item(&mut self, message: String, path: &Path) {
+        let edit = self.list_form(path);
+        if let Some(d) = self.push(
+            DiagnosticLevel::Info,
+            "type-coerced",
+            message,
+            path,
+            Span::Value,
+            None,
+        ) {
+            d.fixes.extend(edit.map(|edit| CodeFix {
+                title: "Write as a list".to_string(),
+                kind: "quickfix".to_string(),
+                edit: Some(edit),
+                path: None,
+                value: None,
+            }));
+            Self::at_path(Some(d), path);
+        }
+    }
+
+    fn list_form(&self, path: &Path) -> Option<TextEdit> {
+        let entry = self
+            .source
+            .get_or_init(|| SourceMap::new(self.input))
+            .get(path)?;
+        let inline = source_map::slice(self.input, &entry.value).filter(|text| !text.is_empty());
+        if let Some(text) = inline {
+            return (!text.starts_with(['|', '>'])).then(|| TextEdit {
+                range: entry.value.clone(),
+                new_text: format!("[{}]", text),
+            });
+        }
+
+        // The nested lines, with the first content line marked as the item
+        // and the rest indented under it
+        let first = entry.value.start.line as usize + 1;
+        let last = entry.full.end.line as usize;
+        let lines: Vec<&str> = self.input.lines().take(last + 1).skip(first).collect();
+        if lines.is_empty() {
+            return None;
+        }
+        let mut marked = false;
+        let new_text: Vec<String> = lines
+            .iter()
+            .map(|line| {
+                let content = line.trim_start();
+                if !marked && !content.is_empty() && !content.starts_with('#') {
+                    marked = true;
+                    format!("{}- {}", &line[..line.len() - content.len()], content)
+                } else if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("  {}", line)
+                }
+            })
+            .collect();
+        Some(TextEdit {
+            range: Range {
+                start: Position {
+                    line: first as u32,
+                    character: 0,
+                },
+                end: Position {
+                    line: last as u32,
+                    character: lines[lines.len() - 1].encode_utf16().count() as u32,
+                },
+            },
+            new_text: new_text.join("\n"),
+        })
+    }
+
+    fn expanded(&mut self, message: String, path: &Path, span: Span, new_text: String) {
+        let d = self.push(
+            DiagnosticLevel::Info,
+            "shorthand-expanded",
+            message,
+            path,
+            span,
+            Some(("Expand to canonical form".to_string(), span, new_text)),
+        );
+        Self::at_path(d, path);
+    }
+
+    /// A shorthand key expanded to entries of `field`. The fix rewrites it
+    /// as a `field:` block, so it is only offered when no other key writes
+    /// `field`: a second `field:` key would be a duplicate key, which YAML
+    /// rejects.
+    fn shorthand(&mut self, message: String, path: &Path, field: &str, entries: Vec<String>) {
+        if self.shared_fields.contains(field) {
+            let d = self.push(
+                DiagnosticLevel::Info,
+                "shorthand-expanded",
+                message,
+                path,
+                Span::Full,
+                None,
+            );
+            Self::at_path(d, path);
+        } else {
+            let new_text = format!("{}:\n{}", field, entries.join("\n"));
+            self.expanded(message, path, Span::Full, new_text);
+        }
+    }
+
+    /// A misspelled key read as its nearest known key
+    fn typo_corrected(&mut self, written: &str, suggestion: &KeySuggestion, path: &Path) {
+        if let Some(d) = self.push(
+            DiagnosticLevel::Warning,
+            "key-typo-corrected",
+            format!(
+                "\"{}\" read as \"{}\" (confidence {:.2})",
+                written, suggestion.key, suggestion.confidence
+            ),
+            path,
+            Span::Key,
+            Some((
+                format!("Rename to {}", suggestion.key),
+                Span::Key,
+                suggestion.key.clone(),
+            )),
+        ) {
+            d.message_args = Some(MessageArgs::new(
+                "key-typo-corrected",
+                [
+                    ("written", written.to_string()),
+                    ("suggestion", suggestion.key.clone()),
+                    ("confidence", format!("{:.2}", suggestion.confidence)),
+                ],
+            ));
+        }
+    }
+
+    /// An unknown key left out, with the known key it may have meant
+    fn typo(&mut self, written: &str, suggestion: &KeySuggestion, path: &Path) {
+        if let Some(d) = self.push(
+            DiagnosticLevel::Warning,
+            "key-typo",
+            format!(
+                "Unknown field \"{}\" ignored; did you mean \"{}\"?",
+                written, suggestion.key
+            ),
+            path,
+            Span::Key,
+            Some((
+                format!("Rename to {}", suggestion.key),
+                Span::Key,
+                suggestion.key.clone(),
+            )),
+        ) {
+            d.message_args = Some(MessageArgs::new(
+                "key-typo",
+                [
+                    ("written", written.to_string()),
+                    ("suggestion", suggestion.key.clone()),
+                    ("confidence", format!("{:.2}", suggestion.confidence)),
+                ],
+            ));
+        }
+    }
+
+    fn locale_detected(&mut self, guess: &LocaleGuess) {
+        if !self.collect {
+            return;
+        }
+        let (message, id) = match guess.source {
+            LocaleSource::Metadata => (
+                format!("Locale {} taken from metadata.locale", guess.locale),
+                "locale-detected.metadata",
+            ),
+            _ => (
+                format!(
+                    "Locale detected as {} (confidence {:.2}) from keys and scripts",
+                    guess.locale, guess.confidence
+                ),
+                "locale-detected",
+            ),
+        };
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Info,
+            message,
+            code: Some("locale-detected".to_string()),
+            range: None,
+            fixes: vec![],
+            path: None,
+            message_args: Some(MessageArgs::new(
+                id,
+                [
+                    ("locale", guess.locale.clone()),
+                    ("confidence", format!("{:.2}", guess.confidence)),
+                ],
+            )),
+        });
+    }
+
+    /// A value the parser ignored; the fix deletes its lines. A value that
+    /// shares its first line with other content (an item of a flow list, a
+    /// key after `- `) gets no fix, as deleting the lines would take that
+    /// content with it.
+    fn dropped(&mut self, message: String, path: &Path) {
+        if !self.collect {
+            return;
+        }
+        let Some(range) = self.range(path, Span::Full) else {
+            let d = self.push(
+                DiagnosticLevel::Warning,
+                "value-dropped",
+                message,
+                path,
+                Span::Full,
+                None,
+            );
+            Self::at_path(d, path);
+            return;
+        };
+        let starts_line = self
+            .source
+            .get()
+            .and_then(|s| s.get(path))
+            .is_some_and(|e| e.starts_line);
+        let fixes = if starts_line {
+            vec![CodeFix {
+                title: "Remove ignored value".to_string(),
+                kind: "quickfix".to_string(),
+                edit: Some(TextEdit {
+                    range: Range {
+                        start: Position {
+                            line: range.start.line,
+                            character: 0,
+                        },
+                        end: Position {
+                            line: range.end.line + 1,
+                            character: 0,
+                        },
+                    },
+                    new_text: String::new(),
+                }),
+                path: None,
+                value: None,
+            }]
+        } else {
+            vec![]
+        };
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message,
+            code: Some("value-dropped".to_string()),
+            range: Some(range),
+            fixes,
+            path: None,
+            message_args: Some(MessageArgs::new(
+                "value-dropped",
+                [("path", path_pointer(path))],
+            )),
+        });
+    }
+}
+
+/// Render a scalar list as a YAML flow sequence
+fn flow_list(items: &[String]) -> String {
+    format!("[{}]", items.join(", "))
+}
+
+/// `s` as a double-quoted YAML scalar. Control, bidi and invisible
+/// characters are escaped so a fix shows exactly what it inserts.
+pub(crate) fn yaml_string(s: &str) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control()
+                || confusables::is_bidi_control(c)
+                || confusables::is_invisible(c)
+                || matches!(c, '\u{200C}' | '\u{200D}' | '\u{2028}' | '\u{2029}') =>
+            {
+                let _ = match c as u32 {
+                    n @ 0..=0xFF => write!(out, "\\x{:02X}", n),
+                    n @ 0x100..=0xFFFF => write!(out, "\\u{:04X}", n),
+                    n => write!(out, "\\U{:08X}", n),
+                };
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Confidence a misspelled key must exceed to be corrected
+pub const DEFAULT_TYPO_THRESHOLD: f64 = 0.75;
+
 pub struct Parser {
     alias_manager: AliasManager,
+    options: ParseOptions,
+    preference: PreferenceInference,
+    typo_threshold: f64,
 }
 
 impl Parser {
     pub fn new() -> Self {
         Self {
             alias_manager: AliasManager::new(),
+            options: ParseOptions::default(),
+            preference: PreferenceInference::default(),
+            typo_threshold: DEFAULT_TYPO_THRESHOLD,
         }
     }
 
     pub fn with_alias_manager(alias_manager: AliasManager) -> Self {
-        Self { alias_manager }
+        Self {
+            alias_manager,
+            options: ParseOptions::default(),
+            preference: PreferenceInference::default(),
+            typo_threshold: DEFAULT_TYPO_THRESHOLD,
+        }
+    }
+
+    /// Replace the default resource limits
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    pub fn alias_manager(&self) -> &AliasManager {
+        &self.alias_manager
+    }
+
+    /// Mark a preferred phone and email when the input marks none
+    pub fn with_preference(mut self, preference: PreferenceInference) -> Self {
+        self.preference = preference;
+        self
+    }
+
+    /// Read a misspelled key as its nearest known key only above this
+    /// confidence; at 1.0 or more unknown keys are only reported
+    pub fn with_typo_threshold(mut self, threshold: f64) -> Self {
+        self.typo_threshold = threshold;
+        self
     }
 
     /// Parse yCard from YAML text with lenient mode
     pub fn parse_lenient(&self, input: &str, locale: Option<&str>) -> Result<YCard, ParseError> {
         // First parse as generic YAML
-        let value: Value = serde_yaml::from_str(input)?;
+        let value = parse_yaml(input, &self.options)?;
 
         // Convert to our schema with normalization
-        self.value_to_ycard(value, locale)
+        self.value_to_ycard(value, locale, &mut Report::disabled(input))
     }
 
-    /// Parse yCard from YAML text with strict mode  
+    /// Parse leniently and report how the input was interpreted: alias
+    /// resolution, shorthand expansion, phone normalization, type coercion
+    /// and dropped values, each located in `input` with a fix
+    pub fn parse_lenient_with_diagnostics(
+        &self,
+        input: &str,
+        locale: Option<&str>,
+    ) -> Result<(YCard, Vec<Diagnostic>), ParseError> {
+        let value = parse_yaml(input, &self.options)?;
+        let mut report = Report::collecting(input, false);
+        let ycard = self.value_to_ycard(value, locale, &mut report)?;
+        Ok((ycard, report.diagnostics))
+    }
+
+    /// Parse leniently without failing the whole card: fields that cannot be
+    /// read are left out and reported as error diagnostics alongside the
+    /// normalization diagnostics. On a YAML syntax error the lines before the
+    /// error are still read. Exceeding a resource limit yields an empty card.
+    pub fn parse_partial(&self, input: &str, locale: Option<&str>) -> (YCard, Vec<Diagnostic>) {
+        let mut report = Report::collecting(input, true);
+        let value = match parse_yaml(input, &self.options) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                report.diagnostics.push(error.to_diagnostic());
+                match &error {
+                    ParseError::Yaml(e) => self.parse_prefix(input, e),
+                    _ => None,
+                }
+            }
+        };
+
+        let ycard = match value {
+            Some(value) => self
+                .value_to_ycard(value, locale, &mut report)
+                // Field errors are recovered, so only unexpected failures land here
+                .unwrap_or_else(|error| {
+                    report.diagnostics.push(error.to_diagnostic());
+                    YCard::default()
+                }),
+            None => YCard::default(),
+        };
+        (ycard, report.diagnostics)
+    }
+
+    /// The document up to the line of a syntax error. Errors reported at the
+    /// end of input belong to an earlier line, so back off line by line.
+    fn parse_prefix(&self, input: &str, error: &serde_yaml::Error) -> Option<Value> {
+        let mut end = error.location()?.index().min(input.len());
+        for _ in 0..MAX_RECOVERY_ATTEMPTS {
+            // Cut at the start of the line containing `end`, or the previous
+            // line when `end` already sits at a line start
+            end = input[..end.saturating_sub(1)]
+                .rfind('\n')
+                .map_or(0, |nl| nl + 1);
+            if end == 0 {
+                return None;
+            }
+            if let Ok(value) = parse_yaml(&input[..end], &self.options) {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// The locale lenient parsing uses when given none: `metadata.locale`,
+    /// else the best match of the document's keys and scripts
+    pub fn detect_locale(&self, input: &str) -> Result<Option<LocaleGuess>, ParseError> {
+        let value = parse_yaml(input, &self.options)?;
+        Ok(detect_locale(&value, &self.alias_manager))
+    }
+
+    /// Parse yCard from YAML text with strict mode
     pub fn parse_strict(&self, input: &str) -> Result<YCard, ParseError> {
-        let ycard: YCard = serde_yaml::from_str(input)?;
+        let value = parse_yaml(input, &self.options)?;
+        let ycard: YCard = serde_yaml::from_value(value)?;
         Ok(ycard)
     }
 
-    fn value_to_ycard(&self, mut value: Value, locale: Option<&str>) -> Result<YCard, ParseError> {
+    fn value_to_ycard(
+        &self,
+        value: Value,
+        locale: Option<&str>,
+        report: &mut Report<'_>,
+    ) -> Result<YCard, ParseError> {
         let mut ycard = YCard::default();
 
-        if let Value::Mapping(ref mut map) = value {
-            // Process shorthand phone fields first
-            self.extract_shorthand_phones(map, &mut ycard, locale)?;
+        // Without a caller locale, read the document's own
+        let detected = match locale {
+            Some(_) => None,
+            None => detect_locale(&value, &self.alias_manager),
+        };
+        if let Some(guess) = &detected {
+            report.locale_detected(guess);
+        }
+        let locale = locale.or(detected.as_ref().map(|g| g.locale.as_str()));
 
-            // Process regular fields
-            for (key, val) in map.iter() {
-                if let Value::String(key_str) = key {
-                    let normalized_key = self.normalize_field_key(key_str, locale);
+        let Value::Mapping(map) = value else {
+            return Ok(ycard);
+        };
 
-                    match normalized_key.as_str() {
-                        "version" => {
-                            if let Some(v) = val.as_u64() {
-                                ycard.version = v as u8;
-                            }
-                        }
-                        "uid" => {
-                            if let Value::String(s) = val {
-                                ycard.uid = Some(s.clone());
-                            }
-                        }
-                        "name" => {
-                            ycard.name = Some(self.extract_name(val, locale)?);
-                        }
-                        "phones" => {
-                            ycard.phones = Some(self.extract_phones(val, locale)?);
-                        }
-                        "emails" => {
-                            ycard.emails = Some(self.extract_emails(val, locale)?);
-                        }
-                        "addresses" => {
-                            ycard.addresses = Some(self.extract_addresses(val, locale)?);
-                        }
-                        "manager" => {
-                            if let Value::String(s) = val {
-                                ycard.manager = Some(s.clone());
-                            }
-                        }
-                        "metadata" => {
-                            ycard.metadata = Some(self.extract_metadata(val)?);
-                        }
-                        _ => {
-                            // Unknown field - could add to metadata or warn
-                        }
-                    }
+        // Fields written under a known key, which a corrected typo must not
+        // overwrite
+        let mut present = HashSet::new();
+        for target in map
+            .keys()
+            .filter_map(Value::as_str)
+            .filter_map(|key| self.key_target(key, locale))
+        {
+            if !present.insert(target.clone()) {
+                report.shared_fields.insert(target);
+            }
+        }
+
+        for (key, val) in map.iter() {
+            let Some(written) = key.as_str() else {
+                continue;
+            };
+            let path = [PathSegment::Key(written.to_string())];
+
+            // Read a misspelled key as the nearest known one when confident enough
+            let suggestion = if self.is_known_key(written, locale) {
+                None
+            } else {
+                self.suggest_key(written, locale)
+            };
+            let key_str = match &suggestion {
+                Some(s) if s.confidence > self.typo_threshold && !present.contains(&s.target) => {
+                    report.typo_corrected(written, s, &path);
+                    present.insert(s.target.clone());
+                    s.key.as_str()
                 }
+                _ => written,
+            };
+
+            match self.root_key(&mut ycard, written, key_str, val, locale, report) {
+                Ok(true) => {}
+                Ok(false) => match &suggestion {
+                    Some(suggestion) => report.typo(key_str, suggestion, &path),
+                    None => report.dropped(format!("Unknown field \"{}\" ignored", key_str), &path),
+                },
+                // A corrected key is a guess, so a value it can't take is
+                // dropped rather than failing the parse
+                Err(error) if key_str != written => report.dropped(
+                    format!(
+                        "\"{}\" read as \"{}\" but ignored: {}",
+                        written, key_str, error
+                    ),
+                    &path,
+                ),
+                Err(error) => return Err(error),
+            }
+        }
+
+        for pointer in self.preference.apply(&mut ycard) {
+            if report.collect {
+                report.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Info,
+                    message: format!("{} inferred as preferred", pointer),
+                    code: Some("preferred-inferred".to_string()),
+                    range: None,
+                    fixes: vec![],
+                    message_args: Some(MessageArgs::new(
+                        "preferred-inferred",
+                        [("path", pointer.clone())],
+                    )),
+                    path: Some(pointer),
+                });
             }
         }
 
         Ok(ycard)
     }
 
+    /// Read the top-level entry `written`, spelled `key_str` after typo
+    /// correction, into `ycard`. Returns false for an unknown field.
+    fn root_key(
+        &self,
+        ycard: &mut YCard,
+        written: &str,
+        key_str: &str,
+        val: &Value,
+        locale: Option<&str>,
+        report: &mut Report<'_>,
+    ) -> Result<bool, ParseError> {
+        let path: &Path = &[PathSegment::Key(written.to_string())];
+
+        // Shorthand keys (`mobile:`, `work_email:`, `home_address:`) expand to typed entries
+        if let Some(phone_type) = shorthand_type(PHONE_SHORTHANDS, key_str) {
+            let phone_type = PhoneType::from_str_with_locale(phone_type, "en");
+            let phones = self.shorthand_phones(key_str, val, phone_type, locale, path, report);
+            if let Some(phones) = report.recover(phones)? {
+                merge_into(&mut ycard.phones, phones, path, report);
+            }
+            return Ok(true);
+        }
+        if let Some(email_type) = shorthand_type(EMAIL_SHORTHANDS, key_str) {
+            let email_type = EmailType::from_str_with_locale(email_type, "en");
+            let emails = self.shorthand_emails(key_str, val, email_type, path, report);
+            merge_into(&mut ycard.emails, emails, path, report);
+            return Ok(true);
+        }
+        if let Some(address_type) = shorthand_type(ADDRESS_SHORTHANDS, key_str) {
+            let address_type = AddressType::from_str_with_locale(address_type, "en");
+            let addresses =
+                self.shorthand_addresses(key_str, val, address_type, locale, path, report);
+            merge_into(&mut ycard.addresses, addresses, path, report);
+            return Ok(true);
+        }
+
+        let normalized_key = self.normalize_field_key(key_str, locale);
+        let known = is_root_field(&normalized_key) || is_name_field(&normalized_key);
+        if known && normalized_key != key_str && key_str == written {
+            report.alias(key_str, &normalized_key, path, Span::Key);
+        }
+
+        match normalized_key.as_str() {
+            "version" => match val {
+                Value::Number(n) if n.as_u64().is_some_and(|v| v <= u8::MAX as u64) => {
+                    ycard.version = n.as_u64().unwrap_or(1) as u8;
+                }
+                Value::String(s) if s.trim().parse::<u8>().is_ok() => {
+                    ycard.version = s.trim().parse().unwrap_or(1);
+                    report.coerced(
+                        format!("version \"{}\" read as the number {}", s, ycard.version),
+                        path,
+                        Some(ycard.version.to_string()),
+                    );
+                }
+                _ => report.dropped(
+                    "version must be a small integer; value ignored".to_string(),
+                    path,
+                ),
+            },
+            "uid" | "manager" => match scalar_to_string(val) {
+                Some(s) => {
+                    if !val.is_string() {
+                        report.coerced(
+                            format!("{} read as the string \"{}\"", normalized_key, s),
+                            path,
+                            Some(yaml_string(&s)),
+                        );
+                    }
+                    if normalized_key == "uid" {
+                        ycard.uid = Some(s);
+                    } else {
+                        ycard.manager = Some(s);
+                    }
+                }
+                None => report.dropped(
+                    format!("{} must be a string; value ignored", normalized_key),
+                    path,
+                ),
+            },
+            "name" => {
+                let name = self.extract_name(val, locale, path, report);
+                if let Some(mut name) = report.recover(name)? {
+                    // Keep name fields written at the top level before it
+                    if let Some(earlier) = ycard.name.take() {
+                        fill_name(&mut name, earlier);
+                    }
+                    ycard.name = Some(name);
+                }
+            }
+            // Name fields written at the top level, e.g. `ふりがな:`
+            key if is_name_field(key) => {
+                let field = &key["name.".len()..];
+                let name = ycard.name.get_or_insert_with(Name::default);
+                self.name_field(name, field, val, locale, path, report)?;
+            }
+            "nickname" => {
+                let nicknames = self.value_to_string_vec(val, path, report);
+                ycard
+                    .nickname
+                    .get_or_insert_with(Vec::new)
+                    .extend(nicknames);
+            }
+            "pronouns" => match val {
+                Value::Sequence(seq) if seq.iter().all(Value::is_string) => {
+                    let pronouns = seq
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    report.coerced(
+                        format!("pronouns read as \"{}\"", pronouns),
+                        path,
+                        Some(yaml_string(&pronouns)),
+                    );
+                    ycard.pronouns = Some(pronouns);
+                }
+                _ => match val.as_str() {
+                    Some(s) => ycard.pronouns = Some(s.to_string()),
+                    None => {
+                        report.dropped("pronouns must be a string; value ignored".to_string(), path)
+                    }
+                },
+            },
+            "gender" => {
+                if let Some(gender) = self.extract_gender(val, locale, path, report) {
+                    ycard.gender = Some(gender);
+                }
+            }
+            "languages" => {
+                let languages = self.extract_languages(val, path, report);
+                merge_into(&mut ycard.languages, languages, path, report);
+            }
+            "phones" => {
+                let phones = self.extract_phones(val, locale, path, report);
+                if let Some(phones) = report.recover(phones)? {
+                    merge_into(&mut ycard.phones, phones, path, report);
+                }
+            }
+            "emails" => {
+                let emails = self.extract_emails(val, locale, path, report);
+                if let Some(emails) = report.recover(emails)? {
+                    merge_into(&mut ycard.emails, emails, path, report);
+                }
+            }
+            "addresses" => {
+                let addresses = self.extract_addresses(val, locale, path, report);
+                if let Some(addresses) = report.recover(addresses)? {
+                    merge_into(&mut ycard.addresses, addresses, path, report);
+                }
+            }
+            "metadata" => {
+                let metadata = self.extract_metadata(val, path, report);
+                if let Some(metadata) = report.recover(metadata)? {
+                    ycard.metadata = Some(metadata);
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
     fn normalize_field_key(&self, key: &str, locale: Option<&str>) -> String {
         self.alias_manager
             .resolve_key_alias(key, locale)
             .unwrap_or_else(|| key.to_string())
     }
 
-    fn extract_shorthand_phones(
+    /// Whether `key` is a root field, one of its aliases or a shorthand
+    fn is_known_key(&self, key: &str, locale: Option<&str>) -> bool {
+        self.key_target(key, locale).is_some()
+    }
+
+    /// The field a known key writes: `phones` for `mobile`, `name` for `nom`
+    fn key_target(&self, key: &str, locale: Option<&str>) -> Option<String> {
+        let shorthands = [
+            (PHONE_SHORTHANDS, "phones"),
+            (EMAIL_SHORTHANDS, "emails"),
+            (ADDRESS_SHORTHANDS, "addresses"),
+        ];
+        if let Some((_, field)) = shorthands
+            .iter()
+            .find(|(table, _)| shorthand_type(table, key).is_some())
+        {
+            return Some(field.to_string());
+        }
+        let key = self.normalize_field_key(key, locale);
+        (is_root_field(&key) || is_name_field(&key)).then_some(key)
+    }
+
+    /// The nearest root field, alias or shorthand to an unknown key
+    fn suggest_key(&self, key: &str, locale: Option<&str>) -> Option<KeySuggestion> {
+        let aliases = self.alias_manager.key_alias_spellings(locale);
+        let fields = ROOT_FIELDS.iter().map(|f| (f.name, f.name));
+        let shorthands = [
+            (PHONE_SHORTHANDS, "phones"),
+            (EMAIL_SHORTHANDS, "emails"),
+            (ADDRESS_SHORTHANDS, "addresses"),
+        ]
+        .into_iter()
+        .flat_map(|(table, field)| table.iter().map(move |(k, _)| (*k, field)));
+        let aliases = aliases
+            .iter()
+            .filter(|(_, field)| is_root_field(field))
+            .map(|(alias, field)| (alias.as_str(), field.as_str()));
+        suggest_key(key, fields.chain(shorthands).chain(aliases))
+    }
+
+    /// Canonical type name for a localized type value
+    fn normalize_type(&self, value: &str, locale: Option<&str>) -> String {
+        self.alias_manager
+            .resolve_type_alias(value, locale)
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    fn shorthand_phones(
         &self,
-        map: &mut serde_yaml::Mapping,
-        ycard: &mut YCard,
+        key: &str,
+        value: &Value,
+        phone_type: PhoneType,
         locale: Option<&str>,
-    ) -> Result<(), ParseError> {
-        // Using generated shorthand keys from schema
-        let _shorthand_keys = PHONE_SHORTHAND_KEYS;
-        let mut shorthand_phones = Vec::new();
-
-        // Check for localized shorthand keys
-        let keys_to_remove: Vec<_> = map
-            .keys()
-            .filter_map(|k| {
-                if let Value::String(key_str) = k {
-                    let normalized = self.normalize_field_key(key_str, locale);
-                    if normalized.starts_with("phones.type:") {
-                        Some(k.clone())
-                    } else {
-                        None
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<Vec<Phone>, ParseError> {
+        let type_name = type_name(&phone_type);
+        let phones = match value {
+            Value::Sequence(items) => {
+                let mut phones = Vec::new();
+                for (i, item) in items.iter().enumerate() {
+                    let item_path = child(path, i);
+                    let phone = self.phone_from_item(
+                        item,
+                        vec![phone_type.clone()],
+                        locale,
+                        &item_path,
+                        report,
+                    );
+                    if let Some(phone) = report.recover(phone)?.flatten() {
+                        phones.push(phone);
                     }
-                } else {
-                    None
                 }
-            })
-            .collect();
+                phones
+            }
+            _ => self
+                .phone_from_item(value, vec![phone_type.clone()], locale, path, report)?
+                .into_iter()
+                .collect(),
+        };
 
-        for key in keys_to_remove {
-            if let Some(value) = map.remove(&key) {
-                if let Value::String(key_str) = &key {
-                    let normalized = self.normalize_field_key(key_str, locale);
-                    if let Some(type_part) = normalized.strip_prefix("phones.type:") {
-                        let phone_type =
-                            PhoneType::from_str_with_locale(type_part, locale.unwrap_or("en"));
-                        let phones = self.value_to_phones(value, vec![phone_type], locale)?;
-                        shorthand_phones.extend(phones);
-                    }
-                }
+        if !phones.is_empty() {
+            let entries: Vec<String> = phones
+                .iter()
+                .map(|p| {
+                    format!(
+                        "  - number: {}\n    type: [{}]",
+                        yaml_string(&p.number),
+                        type_name
+                    )
+                })
+                .collect();
+            report.shorthand(
+                format!("\"{}\" expanded to phones with type {}", key, type_name),
+                path,
+                "phones",
+                entries,
+            );
+        }
+        Ok(phones)
+    }
+
+    fn shorthand_emails(
+        &self,
+        key: &str,
+        value: &Value,
+        email_type: EmailType,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Vec<Email> {
+        let type_name = type_name(&email_type);
+        let items: Vec<(Vec<PathSegment>, &Value)> = match value {
+            Value::Sequence(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| (child(path, i), item))
+                .collect(),
+            _ => vec![(path.to_vec(), value)],
+        };
+
+        let mut emails = Vec::new();
+        for (item_path, item) in items {
+            match item.as_str() {
+                Some(address) => emails.push(Email {
+                    address: address.to_string(),
+                    r#type: vec![email_type.clone()],
+                    preferred: None,
+                }),
+                None => report.dropped(
+                    format!("Email under \"{}\" must be a string; value ignored", key),
+                    &item_path,
+                ),
             }
         }
 
-        if !shorthand_phones.is_empty() {
-            ycard.phones = Some(shorthand_phones);
+        if !emails.is_empty() {
+            let entries: Vec<String> = emails
+                .iter()
+                .map(|e| {
+                    format!(
+                        "  - address: {}\n    type: [{}]",
+                        yaml_string(&e.address),
+                        type_name
+                    )
+                })
+                .collect();
+            report.shorthand(
+                format!("\"{}\" expanded to emails with type {}", key, type_name),
+                path,
+                "emails",
+                entries,
+            );
         }
+        emails
+    }
 
-        Ok(())
+    fn shorthand_addresses(
+        &self,
+        key: &str,
+        value: &Value,
+        address_type: AddressType,
+        locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Vec<Address> {
+        let type_name = type_name(&address_type);
+        let items: Vec<(Vec<PathSegment>, &Value)> = match value {
+            Value::Sequence(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| (child(path, i), item))
+                .collect(),
+            _ => vec![(path.to_vec(), value)],
+        };
+
+        let addresses: Vec<Address> = items
+            .into_iter()
+            .filter_map(|(item_path, item)| {
+                self.address_from_item(item, address_type.clone(), locale, &item_path, report)
+            })
+            .collect();
+
+        if !addresses.is_empty() {
+            let entries: Vec<String> = addresses
+                .iter()
+                .map(|a| match &a.formatted {
+                    Some(formatted) => {
+                        format!(
+                            "  - formatted: {}\n    type: [{}]",
+                            yaml_string(formatted),
+                            type_name
+                        )
+                    }
+                    None => format!("  - type: [{}]", type_name),
+                })
+                .collect();
+            report.shorthand(
+                format!("\"{}\" expanded to addresses with type {}", key, type_name),
+                path,
+                "addresses",
+                entries,
+            );
+        }
+        addresses
     }
 
-    fn extract_name(&self, value: &Value, locale: Option<&str>) -> Result<Name, ParseError> {
+    fn extract_name(
+        &self,
+        value: &Value,
+        locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<Name, ParseError> {
+        let mut name = Name::default();
+
         match value {
             Value::String(s) => {
-                // Simple string name - try to parse into components
-                Ok(Name {
-                    display_name: Some(s.clone()),
-                    given_name: None, // Could implement name parsing logic here
-                    family_name: None,
-                    middle_name: None,
-                    honorific_prefix: None,
-                    honorific_suffix: None,
-                    script: None,
-                })
+                // Simple string name
+                name.display_name = Some(s.clone());
+                // Chinese, Japanese and Thai names split into their parts
+                if let Some((family, given)) = split_name(s) {
+                    report.expanded(
+                        format!(
+                            "name \"{}\" read as familyName \"{}\" and givenName \"{}\"",
+                            s, family, given
+                        ),
+                        path,
+                        Span::Value,
+                        format!(
+                            "{{displayName: {}, familyName: [{}], givenName: [{}]}}",
+                            yaml_string(s),
+                            yaml_string(&family),
+                            yaml_string(&given)
+                        ),
+                    );
+                    name.family_name = Some(vec![family]);
+                    name.given_name = Some(vec![given]);
+                    return Ok(name);
+                }
+                report.expanded(
+                    format!("name \"{}\" read as name.displayName", s),
+                    path,
+                    Span::Value,
+                    format!("{{displayName: {}}}", yaml_string(s)),
+                );
+                Ok(name)
             }
             Value::Mapping(map) => {
-                let mut name = Name {
-                    display_name: None,
-                    given_name: None,
-                    family_name: None,
-                    middle_name: None,
-                    honorific_prefix: None,
-                    honorific_suffix: None,
-                    script: None,
-                };
-
                 for (key, val) in map {
-                    if let Value::String(key_str) = key {
-                        let normalized_key = self.normalize_field_key(key_str, locale);
-                        match normalized_key.as_str() {
-                            "name.givenName" => {
-                                name.given_name = Some(self.value_to_string_vec(val));
-                            }
-                            "name.familyName" => {
-                                name.family_name = Some(self.value_to_string_vec(val));
-                            }
-                            "name.displayName" => {
-                                name.display_name = val.as_str().map(|s| s.to_string());
-                            }
-                            // Add other name fields...
-                            _ => {}
-                        }
+                    let Some(key_str) = key.as_str() else {
+                        continue;
+                    };
+                    let field_path = child(path, key_str);
+                    // Name fields win over root aliases (`language` is not `languages` here)
+                    let normalized_key = if is_name_field(&format!("name.{}", key_str)) {
+                        key_str.to_string()
+                    } else {
+                        self.normalize_field_key(key_str, locale)
+                    };
+                    let canonical = normalized_key
+                        .strip_prefix("name.")
+                        .unwrap_or(&normalized_key);
+
+                    if !self.name_field(&mut name, canonical, val, locale, &field_path, report)? {
+                        report.dropped(
+                            format!("Unknown name field \"{}\" ignored", key_str),
+                            &field_path,
+                        );
+                        continue;
+                    }
+                    if canonical != key_str {
+                        report.alias(key_str, canonical, &field_path, Span::Key);
                     }
                 }
 
                 Ok(name)
             }
-            _ => Err(ParseError::Schema("Invalid name format".to_string())),
+            _ => Err(report.schema_error("Invalid name format", path)),
         }
     }
 
-    fn extract_phones(
+    /// Read one field of a name; `false` when `field` is not a name field
+    fn name_field(
         &self,
-        value: &Value,
+        name: &mut Name,
+        field: &str,
+        val: &Value,
         locale: Option<&str>,
-    ) -> Result<Vec<Phone>, ParseError> {
-        match value {
-            Value::String(s) => {
-                // Single phone number
-                Ok(vec![self.parse_phone_string(
-                    s,
-                    vec![PhoneType::Other],
-                    locale,
-                )?])
-            }
-            Value::Sequence(seq) => {
-                let mut phones = Vec::new();
-                for item in seq {
-                    match item {
-                        Value::String(s) => {
-                            phones.push(self.parse_phone_string(
-                                s,
-                                vec![PhoneType::Other],
-                                locale,
-                            )?);
-                        }
-                        Value::Mapping(_) => {
-                            // Parse as phone object
-                            phones.push(self.parse_phone_object(item, locale)?);
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<bool, ParseError> {
+        let list = |report: &mut Report<'_>| self.value_to_string_vec(val, path, report);
+        let text = || val.as_str().map(|s| s.to_string());
+        match field {
+            "givenName" => name.given_name = Some(list(report)),
+            "middleName" => name.middle_name = Some(list(report)),
+            "familyName" => name.family_name = Some(list(report)),
+            "honorificPrefix" => name.honorific_prefix = Some(list(report)),
+            "honorificSuffix" => name.honorific_suffix = Some(list(report)),
+            "displayName" => name.display_name = text(),
+            "script" => name.script = text(),
+            "language" => name.language = text(),
+            "phoneticGivenName" => name.phonetic_given_name = Some(list(report)),
+            "phoneticFamilyName" => name.phonetic_family_name = Some(list(report)),
+            "phoneticName" => {
+                name.phonetic_name = text();
+                let reading = name.phonetic_name.as_deref().unwrap_or_default();
+                // Kana readings are written family name first; Latin ones
+                // could be either way round and stay whole. There is no fix,
+                // as the reading is kept in phoneticName as written too.
+                let words: Vec<&str> = reading.split_whitespace().collect();
+                if let [family, given] = words[..] {
+                    if is_unspaced_script(reading) {
+                        if let Some(d) = report.push(
+                            DiagnosticLevel::Info,
+                            "shorthand-expanded",
+                            format!(
+                                "Reading \"{}\" read as phoneticFamilyName \"{}\" and phoneticGivenName \"{}\"",
+                                reading, family, given
+                            ),
+                            path,
+                            Span::Value,
+                            None,
+                        ) {
+                            d.message_args = Some(MessageArgs::new(
+                                "shorthand-expanded.reading",
+                                [
+                                    ("reading", reading.to_string()),
+                                    ("family", family.to_string()),
+                                    ("given", given.to_string()),
+                                ],
+                            ));
                         }
-                        _ => {}
+                        name.phonetic_family_name = Some(vec![family.to_string()]);
+                        name.phonetic_given_name = Some(vec![given.to_string()]);
                     }
                 }
-                Ok(phones)
             }
-            Value::Mapping(_) => {
-                // Single phone object
-                Ok(vec![self.parse_phone_object(value, locale)?])
+            "alternates" => {
+                let items = match val {
+                    Value::Sequence(seq) => seq.iter().collect(),
+                    single => vec![single],
+                };
+                let mut alternates = Vec::new();
+                for (i, item) in items.into_iter().enumerate() {
+                    let item_path = match val {
+                        Value::Sequence(_) => child(path, i),
+                        _ => path.to_vec(),
+                    };
+                    let alternate = self.extract_name(item, locale, &item_path, report);
+                    if let Some(alternate) = report.recover(alternate)? {
+                        alternates.push(alternate);
+                    }
+                }
+                name.alternates = Some(alternates);
             }
-            _ => Err(ParseError::Schema("Invalid phones format".to_string())),
+            _ => return Ok(false),
         }
+        Ok(true)
     }
 
-    fn value_to_phones(
+    fn extract_phones(
         &self,
-        value: Value,
-        default_types: Vec<PhoneType>,
+        value: &Value,
         locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
     ) -> Result<Vec<Phone>, ParseError> {
         match value {
-            Value::String(s) => Ok(vec![self.parse_phone_string(&s, default_types, locale)?]),
             Value::Sequence(seq) => {
                 let mut phones = Vec::new();
-                for item in seq {
-                    match item {
-                        Value::String(s) => {
-                            phones.push(self.parse_phone_string(
-                                &s,
-                                default_types.clone(),
-                                locale,
-                            )?);
-                        }
-                        Value::Mapping(_) => {
-                            phones.push(self.parse_phone_object(&item, locale)?);
+                for (i, item) in seq.iter().enumerate() {
+                    let item_path = child(path, i);
+                    let phone = self.phone_from_item(
+                        item,
+                        vec![PhoneType::Other],
+                        locale,
+                        &item_path,
+                        report,
+                    );
+                    if let Some(phone) = report.recover(phone)?.flatten() {
+                        if !item.is_mapping() {
+                            report.expanded(
+                                format!("Phone \"{}\" expanded to a phone entry", phone.number),
+                                &item_path,
+                                Span::Value,
+                                format!(
+                                    "{{number: {}, type: [other]}}",
+                                    yaml_string(&phone.number)
+                                ),
+                            );
                         }
-                        _ => {}
+                        phones.push(phone);
                     }
                 }
                 Ok(phones)
             }
-            _ => Ok(vec![self.parse_phone_string(
-                value.as_str().unwrap_or(""),
+            Value::String(_) | Value::Number(_) | Value::Mapping(_) => {
+                // Single phone number or object
+                let phone =
+                    self.phone_from_item(value, vec![PhoneType::Other], locale, path, report)?;
+                if phone.is_some() {
+                    report.single_item(
+                        "Single phone read as a one-element phones list".to_string(),
+                        path,
+                    );
+                }
+                Ok(phone.into_iter().collect())
+            }
+            _ => Err(report.schema_error("Invalid phones format", path)),
+        }
+    }
+
+    /// A phone from a string, number or object; other values are dropped
+    fn phone_from_item(
+        &self,
+        item: &Value,
+        default_types: Vec<PhoneType>,
+        locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<Option<Phone>, ParseError> {
+        match item {
+            Value::String(s) => Ok(Some(self.parse_phone_string(
+                s,
                 default_types,
                 locale,
-            )?]),
+                path,
+                report,
+            )?)),
+            Value::Number(n) => {
+                let s = n.to_string();
+                report.coerced(
+                    format!("Phone number {} read as a string", s),
+                    path,
+                    Some(yaml_string(&s)),
+                );
+                Ok(Some(self.parse_phone_string(
+                    &s,
+                    default_types,
+                    locale,
+                    path,
+                    report,
+                )?))
+            }
+            Value::Mapping(_) => Ok(Some(self.parse_phone_object(item, locale, path, report)?)),
+            _ => {
+                report.dropped(
+                    "Phone entry must be a string or object; value ignored".to_string(),
+                    path,
+                );
+                Ok(None)
+            }
         }
     }
 
@@ -286,6 +1482,8 @@ impl Parser {
         s: &str,
         default_types: Vec<PhoneType>,
         _locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
     ) -> Result<Phone, ParseError> {
         // Simple phone parsing - in real implementation would use libphonenumber
         let (number, ext) = if let Some(ext_pos) = s.find("ext") {
@@ -299,79 +1497,190 @@ impl Parser {
             (s.trim(), None)
         };
 
-        // Normalize to E.164 (simplified)
-        let normalized = self.normalize_phone_number(number)?;
+        // Normalize to E.164 (simplified)
+        let normalized = self.normalize_phone_number(number, path, report)?;
+        self.report_phone_normalized(s, &normalized, ext.as_deref(), path, report);
+
+        Ok(Phone {
+            number: normalized,
+            r#type: default_types,
+            ext,
+            preferred: None,
+            label: None,
+        })
+    }
+
+    fn report_phone_normalized(
+        &self,
+        written: &str,
+        normalized: &str,
+        ext: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) {
+        if written == normalized {
+            return;
+        }
+        let (message, args) = match ext {
+            Some(ext) => (
+                format!(
+                    "Phone number normalized to E.164: {} (extension {})",
+                    normalized, ext
+                ),
+                MessageArgs::new(
+                    "phone-normalized.extension",
+                    [("number", normalized.to_string()), ("ext", ext.to_string())],
+                ),
+            ),
+            None => (
+                format!("Phone number normalized to E.164: {}", normalized),
+                MessageArgs::new("phone-normalized", [("number", normalized.to_string())]),
+            ),
+        };
+        let d = report.push(
+            DiagnosticLevel::Warning,
+            "phone-normalized",
+            message,
+            path,
+            Span::Value,
+            // An extension cannot live in a bare number, so it moves to `ext`
+            Some(match ext {
+                Some(ext) => (
+                    "Use E.164 format with ext".to_string(),
+                    Span::Value,
+                    format!(
+                        "{{number: {}, ext: {}}}",
+                        yaml_string(normalized),
+                        yaml_string(ext)
+                    ),
+                ),
+                None => (
+                    "Use E.164 format".to_string(),
+                    Span::Value,
+                    yaml_string(normalized),
+                ),
+            }),
+        );
+        if let Some(d) = d {
+            d.message_args = Some(args);
+        }
+    }
+
+    fn parse_phone_object(
+        &self,
+        value: &Value,
+        locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<Phone, ParseError> {
+        let Value::Mapping(map) = value else {
+            return Err(report.schema_error("Invalid phone object", path));
+        };
 
-        Ok(Phone {
-            number: normalized,
-            r#type: default_types,
-            ext,
+        let mut phone = Phone {
+            number: String::new(),
+            r#type: vec![PhoneType::Other],
+            ext: None,
             preferred: None,
             label: None,
-        })
-    }
+        };
 
-    fn parse_phone_object(&self, value: &Value, locale: Option<&str>) -> Result<Phone, ParseError> {
-        if let Value::Mapping(map) = value {
-            let mut phone = Phone {
-                number: String::new(),
-                r#type: vec![PhoneType::Other],
-                ext: None,
-                preferred: None,
-                label: None,
+        for (key, val) in map {
+            let Some(key_str) = key.as_str() else {
+                continue;
             };
-
-            for (key, val) in map {
-                if let Value::String(key_str) = key {
-                    match key_str.as_str() {
-                        "number" => {
-                            if let Some(num) = val.as_str() {
-                                phone.number = self.normalize_phone_number(num)?;
-                            }
+            let field_path = child(path, key_str);
+            match key_str {
+                "number" => {
+                    if let Some(num) = scalar_to_string(val) {
+                        if !val.is_string() {
+                            report.coerced(
+                                format!("Phone number {} read as a string", num),
+                                &field_path,
+                                Some(yaml_string(&num)),
+                            );
                         }
-                        "type" => {
-                            phone.r#type = self.parse_phone_types(val, locale)?;
-                        }
-                        "ext" => {
-                            phone.ext = val.as_str().map(|s| s.to_string());
-                        }
-                        "preferred" => {
-                            phone.preferred = val.as_bool();
-                        }
-                        "label" => {
-                            phone.label = val.as_str().map(|s| s.to_string());
-                        }
-                        _ => {}
+                        phone.number = self.normalize_phone_number(&num, &field_path, report)?;
+                        self.report_phone_normalized(
+                            &num,
+                            &phone.number,
+                            None,
+                            &field_path,
+                            report,
+                        );
                     }
                 }
+                "type" => {
+                    phone.r#type = self
+                        .parse_types(val, locale, &field_path, report)
+                        .iter()
+                        .map(|t| PhoneType::from_str_with_locale(t, locale.unwrap_or("en")))
+                        .collect();
+                }
+                "ext" => {
+                    phone.ext = scalar_to_string(val);
+                }
+                "preferred" => {
+                    phone.preferred = val.as_bool();
+                }
+                "label" => {
+                    phone.label = val.as_str().map(|s| s.to_string());
+                }
+                _ => report.dropped(
+                    format!("Unknown phone field \"{}\" ignored", key_str),
+                    &field_path,
+                ),
             }
-
-            Ok(phone)
-        } else {
-            Err(ParseError::Schema("Invalid phone object".to_string()))
         }
+
+        Ok(phone)
     }
 
-    fn parse_phone_types(
+    /// Canonical type names from a type value, reporting aliases and scalar coercion
+    fn parse_types(
         &self,
         value: &Value,
         locale: Option<&str>,
-    ) -> Result<Vec<PhoneType>, ParseError> {
-        match value {
-            Value::String(s) => Ok(vec![PhoneType::from_str_with_locale(
-                s,
-                locale.unwrap_or("en"),
-            )]),
-            Value::Sequence(seq) => Ok(seq
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Vec<String> {
+        let items: Vec<(Vec<PathSegment>, &str)> = match value {
+            Value::String(s) => vec![(path.to_vec(), s.as_str())],
+            Value::Sequence(seq) => seq
                 .iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| PhoneType::from_str_with_locale(s, locale.unwrap_or("en")))
-                .collect()),
-            _ => Ok(vec![PhoneType::Other]),
+                .enumerate()
+                .filter_map(|(i, v)| v.as_str().map(|s| (child(path, i), s)))
+                .collect(),
+            _ => vec![],
+        };
+
+        let types: Vec<String> = items
+            .iter()
+            .map(|(item_path, s)| {
+                let canonical = self.normalize_type(s, locale);
+                if canonical != *s {
+                    report.alias(s, &canonical, item_path, Span::Value);
+                }
+                canonical
+            })
+            .collect();
+
+        if value.is_string() {
+            report.coerced(
+                "Single type read as a one-element list".to_string(),
+                path,
+                Some(flow_list(&types)),
+            );
         }
+        types
     }
 
-    fn normalize_phone_number(&self, number: &str) -> Result<String, ParseError> {
+    fn normalize_phone_number(
+        &self,
+        number: &str,
+        path: &Path,
+        report: &Report<'_>,
+    ) -> Result<String, ParseError> {
         // Simplified phone normalization - real implementation would use phonenumber crate
         let digits_only: String = number
             .chars()
@@ -384,10 +1693,7 @@ impl Parser {
             // Assume US number if no country code
             Ok(format!("+1{}", digits_only))
         } else {
-            Err(ParseError::Phone(format!(
-                "Invalid phone number: {}",
-                number
-            )))
+            Err(report.phone_error(format!("Invalid phone number: {}", number), path))
         }
     }
 
@@ -395,134 +1701,584 @@ impl Parser {
         &self,
         value: &Value,
         locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
     ) -> Result<Vec<Email>, ParseError> {
-        // Similar to phones but simpler
+        let email_string = |s: &str| Email {
+            address: s.to_string(),
+            r#type: vec![EmailType::Other],
+            preferred: None,
+        };
+
         match value {
-            Value::String(s) => Ok(vec![Email {
-                address: s.clone(),
-                r#type: vec![EmailType::Other],
-                preferred: None,
-            }]),
+            Value::String(s) => {
+                report.single_item(
+                    "Single email read as a one-element emails list".to_string(),
+                    path,
+                );
+                Ok(vec![email_string(s)])
+            }
+            Value::Mapping(_) => {
+                report.single_item(
+                    "Single email read as a one-element emails list".to_string(),
+                    path,
+                );
+                Ok(vec![self.parse_email_object(value, locale, path, report)?])
+            }
             Value::Sequence(seq) => {
                 let mut emails = Vec::new();
-                for item in seq {
+                for (i, item) in seq.iter().enumerate() {
+                    let item_path = child(path, i);
                     match item {
                         Value::String(s) => {
-                            emails.push(Email {
-                                address: s.clone(),
-                                r#type: vec![EmailType::Other],
-                                preferred: None,
-                            });
+                            report.expanded(
+                                format!("Email \"{}\" expanded to an email entry", s),
+                                &item_path,
+                                Span::Value,
+                                format!("{{address: {}, type: [other]}}", yaml_string(s)),
+                            );
+                            emails.push(email_string(s));
                         }
                         Value::Mapping(_) => {
-                            emails.push(self.parse_email_object(item, locale)?);
+                            emails.push(self.parse_email_object(item, locale, &item_path, report)?);
                         }
-                        _ => {}
+                        _ => report.dropped(
+                            "Email entry must be a string or object; value ignored".to_string(),
+                            &item_path,
+                        ),
                     }
                 }
                 Ok(emails)
             }
-            _ => Ok(vec![]),
+            _ => {
+                report.dropped("emails must be a list; value ignored".to_string(), path);
+                Ok(vec![])
+            }
         }
     }
 
-    fn parse_email_object(&self, value: &Value, locale: Option<&str>) -> Result<Email, ParseError> {
-        if let Value::Mapping(map) = value {
-            let mut email = Email {
-                address: String::new(),
-                r#type: vec![EmailType::Other],
-                preferred: None,
+    fn parse_email_object(
+        &self,
+        value: &Value,
+        locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<Email, ParseError> {
+        let Value::Mapping(map) = value else {
+            return Err(report.schema_error("Invalid email object", path));
+        };
+
+        let mut email = Email {
+            address: String::new(),
+            r#type: vec![EmailType::Other],
+            preferred: None,
+        };
+
+        for (key, val) in map {
+            let Some(key_str) = key.as_str() else {
+                continue;
             };
+            let field_path = child(path, key_str);
+            match key_str {
+                "address" => {
+                    email.address = val.as_str().unwrap_or("").to_string();
+                }
+                "type" => {
+                    email.r#type = self
+                        .parse_types(val, locale, &field_path, report)
+                        .iter()
+                        .map(|t| EmailType::from_str_with_locale(t, locale.unwrap_or("en")))
+                        .collect();
+                }
+                "preferred" => {
+                    email.preferred = val.as_bool();
+                }
+                _ => report.dropped(
+                    format!("Unknown email field \"{}\" ignored", key_str),
+                    &field_path,
+                ),
+            }
+        }
 
-            for (key, val) in map {
-                if let Value::String(key_str) = key {
-                    match key_str.as_str() {
-                        "address" => {
-                            email.address = val.as_str().unwrap_or("").to_string();
-                        }
+        Ok(email)
+    }
+
+    fn extract_addresses(
+        &self,
+        value: &Value,
+        locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<Vec<Address>, ParseError> {
+        match value {
+            Value::Sequence(seq) => {
+                let mut addresses = Vec::new();
+                for (i, item) in seq.iter().enumerate() {
+                    let item_path = child(path, i);
+                    if let Some(address) =
+                        self.address_from_item(item, AddressType::Other, locale, &item_path, report)
+                    {
+                        report_formatted_address(item, &item_path, report);
+                        addresses.push(address);
+                    }
+                }
+                Ok(addresses)
+            }
+            _ => {
+                let address =
+                    self.address_from_item(value, AddressType::Other, locale, path, report);
+                if address.is_some() {
+                    report_formatted_address(value, path, report);
+                    report.single_item(
+                        "Single address read as a one-element addresses list".to_string(),
+                        path,
+                    );
+                }
+                Ok(address.into_iter().collect())
+            }
+        }
+    }
+
+    /// An address from a string (the formatted form) or object; other values are dropped
+    fn address_from_item(
+        &self,
+        value: &Value,
+        default_type: AddressType,
+        locale: Option<&str>,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Option<Address> {
+        let mut address = Address {
+            r#type: vec![default_type],
+            formatted: None,
+            components: None,
+        };
+
+        match value {
+            Value::String(s) => {
+                address.formatted = Some(s.clone());
+                Some(address)
+            }
+            Value::Mapping(map) => {
+                for (key, val) in map {
+                    let Some(key_str) = key.as_str() else {
+                        continue;
+                    };
+                    let field_path = child(path, key_str);
+                    match key_str {
                         "type" => {
-                            email.r#type = self.parse_email_types(val, locale)?;
+                            address.r#type = self
+                                .parse_types(val, locale, &field_path, report)
+                                .iter()
+                                .map(|t| {
+                                    AddressType::from_str_with_locale(t, locale.unwrap_or("en"))
+                                })
+                                .collect();
                         }
-                        "preferred" => {
-                            email.preferred = val.as_bool();
+                        "formatted" => address.formatted = val.as_str().map(|s| s.to_string()),
+                        "components" => {
+                            address.components =
+                                Some(self.extract_address_components(val, &field_path, report));
                         }
-                        _ => {}
+                        _ => report.dropped(
+                            format!("Unknown address field \"{}\" ignored", key_str),
+                            &field_path,
+                        ),
                     }
                 }
+                Some(address)
+            }
+            _ => {
+                report.dropped(
+                    "Address must be a string or object; value ignored".to_string(),
+                    path,
+                );
+                None
             }
+        }
+    }
 
-            Ok(email)
-        } else {
-            Err(ParseError::Schema("Invalid email object".to_string()))
+    fn extract_address_components(
+        &self,
+        value: &Value,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> AddressComponents {
+        let mut components = AddressComponents {
+            street: None,
+            locality: None,
+            region: None,
+            postal_code: None,
+            country: None,
+        };
+
+        let Value::Mapping(map) = value else {
+            report.dropped(
+                "Address components must be an object; value ignored".to_string(),
+                path,
+            );
+            return components;
+        };
+
+        for (key, val) in map {
+            let Some(key_str) = key.as_str() else {
+                continue;
+            };
+            let field_path = child(path, key_str);
+            let text = scalar_to_string(val);
+            if text.is_some() && !val.is_string() {
+                report.coerced(
+                    format!("{} read as a string", key_str),
+                    &field_path,
+                    text.as_ref().map(|t| yaml_string(t)),
+                );
+            }
+            match key_str {
+                "street" => components.street = text,
+                "locality" => components.locality = text,
+                "region" => components.region = text,
+                "postalCode" => components.postal_code = text,
+                "country" => components.country = text,
+                _ => report.dropped(
+                    format!("Unknown address component \"{}\" ignored", key_str),
+                    &field_path,
+                ),
+            }
         }
+        components
     }
 
-    fn parse_email_types(
+    /// A sex (`female`, `F`, `女性`) or a gender identity in free text, or
+    /// both as a mapping
+    fn extract_gender(
         &self,
         value: &Value,
         locale: Option<&str>,
-    ) -> Result<Vec<EmailType>, ParseError> {
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Option<Gender> {
+        let sex = |s: &str| match Sex::from_str_with_locale(s.trim(), locale.unwrap_or("en")) {
+            Sex::Custom(_) => None,
+            sex => Some(sex),
+        };
         match value {
-            Value::String(s) => Ok(vec![EmailType::from_str_with_locale(
-                s,
-                locale.unwrap_or("en"),
-            )]),
-            Value::Sequence(seq) => Ok(seq
-                .iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| EmailType::from_str_with_locale(s, locale.unwrap_or("en")))
-                .collect()),
-            _ => Ok(vec![EmailType::Other]),
+            Value::String(s) => {
+                let gender = match sex(s) {
+                    Some(sex) => Gender {
+                        sex: Some(sex),
+                        identity: None,
+                    },
+                    None => Gender {
+                        sex: None,
+                        identity: Some(s.clone()),
+                    },
+                };
+                let (field, text) = match &gender.sex {
+                    Some(sex) => ("sex", type_name(sex)),
+                    None => ("identity", yaml_string(s)),
+                };
+                report.expanded(
+                    format!("gender \"{}\" read as gender.{}", s, field),
+                    path,
+                    Span::Value,
+                    format!("{{{}: {}}}", field, text),
+                );
+                Some(gender)
+            }
+            Value::Mapping(map) => {
+                let mut gender = Gender::default();
+                for (key, val) in map {
+                    let Some(key_str) = key.as_str() else {
+                        continue;
+                    };
+                    let field_path = child(path, key_str);
+                    match (key_str, val.as_str()) {
+                        ("sex", Some(s)) => match sex(s) {
+                            Some(sex) => gender.sex = Some(sex),
+                            None => report.dropped(
+                                format!(
+                                    "Unknown sex \"{}\" ignored; expected male, female, other, none or unknown",
+                                    s
+                                ),
+                                &field_path,
+                            ),
+                        },
+                        ("identity", Some(s)) => gender.identity = Some(s.to_string()),
+                        _ => report.dropped(
+                            format!("Unknown gender field \"{}\" ignored", key_str),
+                            &field_path,
+                        ),
+                    }
+                }
+                Some(gender)
+            }
+            _ => {
+                report.dropped(
+                    "gender must be a string or an object; value ignored".to_string(),
+                    path,
+                );
+                None
+            }
         }
     }
 
-    fn extract_addresses(
+    /// Language tags as a list, a comma-separated string, or entries with
+    /// `tag` and `preferred`
+    fn extract_languages(
         &self,
-        _value: &Value,
-        _locale: Option<&str>,
-    ) -> Result<Vec<Address>, ParseError> {
-        // Simplified address parsing
-        Ok(vec![])
-    }
-
-    fn extract_metadata(&self, value: &Value) -> Result<Metadata, ParseError> {
-        if let Value::Mapping(map) = value {
-            let mut metadata = Metadata {
-                locale: None,
-                source: None,
-            };
-
-            for (key, val) in map {
-                if let Value::String(key_str) = key {
-                    match key_str.as_str() {
-                        "locale" => {
-                            metadata.locale = val.as_str().map(|s| s.to_string());
-                        }
-                        "source" => {
-                            metadata.source = val.as_str().map(|s| s.to_string());
+        value: &Value,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Vec<Language> {
+        let language = |tag: &str| Language {
+            tag: tag.trim().to_string(),
+            preferred: None,
+        };
+        match value {
+            Value::String(s) => {
+                let tags: Vec<String> = s
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                let entries: Vec<String> = tags.iter().map(|t| format!("{{tag: {}}}", t)).collect();
+                report.expanded(
+                    format!(
+                        "languages \"{}\" read as {} language entries",
+                        s,
+                        tags.len()
+                    ),
+                    path,
+                    Span::Value,
+                    flow_list(&entries),
+                );
+                tags.iter().map(|t| language(t)).collect()
+            }
+            Value::Sequence(seq) => {
+                let mut languages = Vec::new();
+                for (i, item) in seq.iter().enumerate() {
+                    let item_path = child(path, i);
+                    match item {
+                        Value::String(tag) => languages.push(language(tag)),
+                        Value::Mapping(map) => {
+                            let mut entry = language("");
+                            for (key, val) in map {
+                                let Some(key_str) = key.as_str() else {
+                                    continue;
+                                };
+                                match (key_str, val) {
+                                    ("tag", Value::String(tag)) => {
+                                        entry.tag = tag.trim().to_string()
+                                    }
+                                    ("preferred", Value::Bool(b)) => entry.preferred = Some(*b),
+                                    _ => report.dropped(
+                                        format!("Unknown language field \"{}\" ignored", key_str),
+                                        &child(&item_path, key_str),
+                                    ),
+                                }
+                            }
+                            if entry.tag.is_empty() {
+                                report.dropped(
+                                    "Language entry without a tag ignored".to_string(),
+                                    &item_path,
+                                );
+                            } else {
+                                languages.push(entry);
+                            }
                         }
-                        _ => {}
+                        _ => report.dropped(
+                            "Language must be a tag or an object; value ignored".to_string(),
+                            &item_path,
+                        ),
                     }
                 }
+                languages
+            }
+            _ => {
+                report.dropped(
+                    "languages must be a list of tags; value ignored".to_string(),
+                    path,
+                );
+                vec![]
             }
+        }
+    }
 
-            Ok(metadata)
-        } else {
-            Err(ParseError::Schema("Invalid metadata format".to_string()))
+    fn extract_metadata(
+        &self,
+        value: &Value,
+        path: &Path,
+        report: &mut Report<'_>,
+    ) -> Result<Metadata, ParseError> {
+        let Value::Mapping(map) = value else {
+            return Err(report.schema_error("Invalid metadata format", path));
+        };
+
+        let mut metadata = Metadata {
+            locale: None,
+            source: None,
+        };
+
+        for (key, val) in map {
+            let Some(key_str) = key.as_str() else {
+                continue;
+            };
+            match key_str {
+                "locale" => {
+                    metadata.locale = val.as_str().map(|s| s.to_string());
+                }
+                "source" => {
+                    metadata.source = val.as_str().map(|s| s.to_string());
+                }
+                _ => report.dropped(
+                    format!("Unknown metadata field \"{}\" ignored", key_str),
+                    &child(path, key_str),
+                ),
+            }
         }
+
+        Ok(metadata)
     }
 
-    fn value_to_string_vec(&self, value: &Value) -> Vec<String> {
+    fn value_to_string_vec(&self, value: &Value, path: &Path, report: &mut Report) -> Vec<String> {
         match value {
-            Value::String(s) => vec![s.clone()],
             Value::Sequence(seq) => seq
                 .iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| s.to_string())
+                .enumerate()
+                .filter_map(|(i, v)| {
+                    let s = scalar_to_string(v);
+                    if s.is_none() {
+                        report.dropped(
+                            "List item must be a string; value ignored".to_string(),
+                            &child(path, i),
+                        );
+                    }
+                    s
+                })
                 .collect(),
-            _ => vec![],
+            _ => match scalar_to_string(value) {
+                Some(s) => {
+                    report.coerced(
+                        format!("\"{}\" read as a one-element list", s),
+                        path,
+                        Some(flow_list(std::slice::from_ref(&s))),
+                    );
+                    vec![s]
+                }
+                None => {
+                    report.dropped("Expected a string or list; value ignored".to_string(), path);
+                    vec![]
+                }
+            },
+        }
+    }
+}
+
+/// Add entries gathered under the key at `path`, merging duplicates of
+/// entries already read from this or an earlier key. Merges get no fix:
+/// the earlier entry takes types and fields from the duplicate, so deleting
+/// the duplicate would lose them.
+fn merge_into<T: Merge>(
+    entries: &mut Option<Vec<T>>,
+    new: Vec<T>,
+    path: &Path,
+    report: &mut Report<'_>,
+) {
+    let entries = entries.get_or_insert_with(Vec::new);
+    let mut index = MergeIndex::new(entries);
+    for entry in new {
+        let description = entry.describe();
+        if let Some(merged) = merge_entry(entries, &mut index, entry) {
+            let message = format!(
+                "Duplicate {} {} merged into an earlier entry ({})",
+                T::KIND,
+                description,
+                merged.describe()
+            );
+            let args = MessageArgs::new(
+                "entry-merged",
+                [("value", description), ("merged", merged.describe())],
+            );
+            if let Some(d) = report.push(
+                DiagnosticLevel::Info,
+                "entry-merged",
+                message,
+                path,
+                Span::Key,
+                None,
+            ) {
+                d.message_args = Some(args);
+            }
+        }
+    }
+}
+
+/// Whether `name` is a top-level field of the schema
+fn is_root_field(name: &str) -> bool {
+    ROOT_FIELDS.iter().any(|f| f.name == name)
+}
+
+/// Whether `key` is a name field as a key alias target, e.g. `name.phoneticName`
+pub(crate) fn is_name_field(key: &str) -> bool {
+    key.strip_prefix("name.").is_some_and(|field| {
+        TYPE_DEFS
+            .iter()
+            .filter(|def| def.name == "Name")
+            .any(|def| def.fields.iter().any(|f| f.name == field))
+    })
+}
+
+/// Fill the fields `name` leaves unset from `other`
+fn fill_name(name: &mut Name, other: Name) {
+    fn fill<T>(field: &mut Option<T>, other: Option<T>) {
+        if field.is_none() {
+            *field = other;
         }
     }
+    fill(&mut name.given_name, other.given_name);
+    fill(&mut name.middle_name, other.middle_name);
+    fill(&mut name.family_name, other.family_name);
+    fill(&mut name.honorific_prefix, other.honorific_prefix);
+    fill(&mut name.honorific_suffix, other.honorific_suffix);
+    fill(&mut name.display_name, other.display_name);
+    fill(&mut name.script, other.script);
+    fill(&mut name.phonetic_given_name, other.phonetic_given_name);
+    fill(&mut name.phonetic_family_name, other.phonetic_family_name);
+    fill(&mut name.phonetic_name, other.phonetic_name);
+    fill(&mut name.language, other.language);
+    fill(&mut name.alternates, other.alternates);
+}
+
+/// Canonical type for a shorthand key in a generated `(key, type)` table.
+/// Case, `-` and `_` are not significant (`Work-Email` matches `work_email`).
+fn shorthand_type(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    let fold = |k: &str| k.to_lowercase().replace('-', "_");
+    let key = fold(key);
+    table
+        .iter()
+        .find(|(candidate, _)| fold(candidate) == key)
+        .map(|(_, canonical)| *canonical)
+}
+
+/// Report a string address read as the formatted form of a `type: [other]` entry
+fn report_formatted_address(value: &Value, path: &Path, report: &mut Report<'_>) {
+    if let Some(formatted) = value.as_str() {
+        report.expanded(
+            "Address string read as the formatted address".to_string(),
+            path,
+            Span::Value,
+            format!("{{formatted: {}, type: [other]}}", yaml_string(formatted)),
+        );
+    }
+}
+
+/// Canonical serialized name of a generated enum value
+fn type_name<T: serde::Serialize>(value: &T) -> String {
+    serde_yaml::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_default()
 }
 
 impl Default for Parser {
@@ -548,13 +2304,12 @@ mobile: "+1 555 123 4567"
         assert_eq!(result.version, 1);
         assert!(result.name.is_some());
         assert!(result.phones.is_some());
+        assert_eq!(result.phones.unwrap()[0].r#type, vec![PhoneType::Mobile]);
     }
 
     #[test]
     fn test_localized_keys() {
         let parser = Parser::new();
-        // TODO: This test should work with dotted path aliases like nom->name.familyName
-        // Currently using simple aliases that work: phone->phones, email->emails
         let input = r#"
 phone: "06 12 34 56 78"
 email: "test@example.com"
@@ -563,5 +2318,482 @@ email: "test@example.com"
         let result = parser.parse_lenient(input, Some("fr")).unwrap();
         assert!(result.phones.is_some());
         assert!(result.emails.is_some());
+
+        // Given-name and surname keys fill in their parts of the name
+        let input = "Vorname: Hans\nNachname: Müller\n";
+        let name = parser
+            .parse_lenient(input, Some("de"))
+            .unwrap()
+            .name
+            .unwrap();
+        assert_eq!(name.given_name, Some(vec!["Hans".to_string()]));
+        assert_eq!(name.family_name, Some(vec!["Müller".to_string()]));
+        let input = "adı: Ayşe\nsoyadı: Yılmaz\n";
+        let name = parser
+            .parse_lenient(input, Some("tr"))
+            .unwrap()
+            .name
+            .unwrap();
+        assert_eq!(name.given_name, Some(vec!["Ayşe".to_string()]));
+        assert_eq!(name.family_name, Some(vec!["Yılmaz".to_string()]));
+    }
+
+    #[test]
+    fn test_detected_locale() {
+        let parser = Parser::new();
+        let input = "nom: Jean Dupont\ncourriel: jean@example.com\nresponsable: Marie\n";
+
+        let guess = parser.detect_locale(input).unwrap().unwrap();
+        assert_eq!(guess.locale, "fr");
+
+        // No locale given: the French keys are still understood
+        let (ycard, diagnostics) = parser.parse_lenient_with_diagnostics(input, None).unwrap();
+        assert!(ycard.emails.is_some());
+        assert_eq!(ycard.manager.as_deref(), Some("Marie"));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.code.as_deref() == Some("locale-detected")));
+
+        // An explicit locale is used as given
+        let ycard = parser.parse_lenient(input, Some("en")).unwrap();
+        assert!(ycard.emails.is_none());
+    }
+
+    #[test]
+    fn test_phonetic_names() {
+        // A reading written before the name is kept when the name arrives
+        let input = "ふりがな: やまだ はなこ\n名前: 山田 花子\n";
+        let parser = Parser::new();
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics(input, Some("ja"))
+            .unwrap();
+        let name = ycard.name.unwrap();
+        assert_eq!(name.phonetic_name.as_deref(), Some("やまだ はなこ"));
+        assert_eq!(name.phonetic_family_name, Some(vec!["やまだ".to_string()]));
+        assert_eq!(name.phonetic_given_name, Some(vec!["はなこ".to_string()]));
+        assert_eq!(name.family_name, Some(vec!["山田".to_string()]));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.code.as_deref() == Some("alias-resolved")
+                && d.message.contains("name.phoneticName")));
+
+        let input = "name:\n  displayName: 王小明\n  拼音: Wáng Xiǎomíng\n  alternates:\n    - Wang Xiaoming\n    - {displayName: Ó Xiaoming, language: ga}\n";
+        let name = parser
+            .parse_lenient(input, Some("zh"))
+            .unwrap()
+            .name
+            .unwrap();
+        // A Latin reading could be in either order and is not split
+        assert_eq!(name.phonetic_name.as_deref(), Some("Wáng Xiǎomíng"));
+        assert_eq!(name.phonetic_family_name, None);
+        let alternates = name.alternates.unwrap();
+        assert_eq!(alternates[0].display_name.as_deref(), Some("Wang Xiaoming"));
+        assert_eq!(alternates[1].language.as_deref(), Some("ga"));
+    }
+
+    #[test]
+    fn test_person_fields() {
+        use crate::validator::{ValidationMode, Validator};
+
+        let input = "\
+nom: Alex Martin
+surnom: Al
+pronoms: [iel, iel]
+genre: femme
+langues: fr, en_US
+languages:
+  - {tag: fr, preferred: true}
+";
+        let parser = Parser::new();
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics(input, Some("fr"))
+            .unwrap();
+        assert_eq!(ycard.nickname, Some(vec!["Al".to_string()]));
+        assert_eq!(ycard.pronouns.as_deref(), Some("iel/iel"));
+        assert_eq!(ycard.gender.as_ref().unwrap().sex, Some(Sex::Female));
+        // The second `fr` merges into the first and marks it preferred
+        let languages = ycard.languages.as_ref().unwrap();
+        assert_eq!(languages.len(), 2);
+        assert_eq!(ycard.preferred_language().unwrap().tag, "fr");
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.code.as_deref() == Some("entry-merged")));
+
+        // Free text is a gender identity, not a sex
+        let ycard = parser
+            .parse_lenient("gender: non-binary\n", Some("en"))
+            .unwrap();
+        let gender = ycard.gender.unwrap();
+        assert_eq!(gender.sex, None);
+        assert_eq!(gender.identity.as_deref(), Some("non-binary"));
+
+        // Language tags are checked like metadata.locale
+        let input = "languages: [en_US, klingon-x]\n";
+        let ycard = parser.parse_lenient(input, Some("en")).unwrap();
+        let diagnostics = Validator::new(ValidationMode::Lenient)
+            .validate(&ycard)
+            .unwrap();
+        let invalid: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.code.as_deref() == Some("locale-invalid"))
+            .collect();
+        assert_eq!(invalid.len(), 2);
+        assert_eq!(invalid[0].fixes[0].value.as_deref(), Some("en-US"));
+        assert_eq!(invalid[0].path.as_deref(), Some("/languages/0/tag"));
+        assert_eq!(invalid[0].fixes[0].title, "Set languages/0/tag to en-US");
+    }
+
+    #[test]
+    fn test_yaml_string() {
+        assert_eq!(yaml_string("+15551234567"), "\"+15551234567\"");
+        assert_eq!(yaml_string("a\u{200B}b"), "\"a\\u200Bb\"");
+        // Fix texts read back as the value they stand for
+        for s in [
+            "Jane \"JD\" Doe",
+            "a\\b",
+            "x\u{202E}y\u{7}",
+            "tab\there",
+            "山田",
+        ] {
+            let value: Value = serde_yaml::from_str(&yaml_string(s)).unwrap();
+            assert_eq!(value.as_str(), Some(s));
+        }
+    }
+
+    #[test]
+    fn test_key_typos() {
+        let input = "emial: jane@example.com\nnmae: Jane Doe\nmobil: \"+1 555 123 4567\"\n";
+        let parser = Parser::new();
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics(input, Some("en"))
+            .unwrap();
+        assert!(ycard.emails.is_some());
+        assert_eq!(ycard.phones.unwrap()[0].r#type, vec![PhoneType::Mobile]);
+        // `nmae` is exactly at the threshold, so it is only suggested
+        assert!(ycard.name.is_none());
+
+        let corrected: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.code.as_deref() == Some("key-typo-corrected"))
+            .collect();
+        assert_eq!(corrected.len(), 2);
+        assert_eq!(
+            corrected[0].fixes[0].edit.as_ref().unwrap().new_text,
+            "email"
+        );
+        assert_eq!(corrected[0].range.as_ref().unwrap().start.line, 0);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.code.as_deref() == Some("key-typo") && d.message.contains("nmae")));
+
+        // A correction never overwrites a field the document already sets
+        let input = "manager: Ann\nmanger: Bob\nname: Jane Doe\nnmae: x\n";
+        let (ycard, diagnostics) = Parser::new()
+            .with_typo_threshold(0.7)
+            .parse_lenient_with_diagnostics(input, Some("en"))
+            .unwrap();
+        assert_eq!(ycard.manager.as_deref(), Some("Ann"));
+        assert_eq!(
+            ycard.name.unwrap().display_name.as_deref(),
+            Some("Jane Doe")
+        );
+        assert!(!diagnostics
+            .iter()
+            .any(|d| d.code.as_deref() == Some("key-typo-corrected")));
+
+        // A corrected key whose value doesn't fit is dropped, not an error
+        let ycard = Parser::new()
+            .parse_lenient("name: Jane\nphone2: x\n", Some("en"))
+            .unwrap();
+        assert!(ycard.phones.is_none());
+
+        // Below the threshold the key is only reported, with a rename fix
+        let parser = Parser::new().with_typo_threshold(0.9);
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics("emial: jane@example.com\n", Some("en"))
+            .unwrap();
+        assert!(ycard.emails.is_none());
+        assert_eq!(diagnostics[0].code.as_deref(), Some("key-typo"));
+        assert!(diagnostics[0].message.contains("did you mean \"email\""));
+        assert_eq!(diagnostics[0].fixes[0].title, "Rename to email");
+
+        // Aliases of the active locale are candidates too
+        let ycard = Parser::new()
+            .parse_lenient("telefone: \"+33 6 12 34 56 78\"\n", Some("fr"))
+            .unwrap();
+        assert!(ycard.phones.is_some());
+    }
+
+    #[test]
+    fn test_normalization_diagnostics() {
+        let parser = Parser::new();
+        let input = "\
+version: 1
+nom: Jean Dupont
+mobile: \"+1 555 123 4567\"
+phones:
+  - number: 5559876543
+    type: cell
+    color: blue
+emails:
+  - jean@example.com
+hobby: chess
+";
+
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics(input, Some("fr"))
+            .unwrap();
+        assert_eq!(ycard.phones.as_ref().map(Vec::len), Some(2));
+
+        let find = |code: &str, line: u32| {
+            diagnostics
+                .iter()
+                .find(|d| {
+                    d.code.as_deref() == Some(code)
+                        && d.range.as_ref().is_some_and(|r| r.start.line == line)
+                })
+                .unwrap_or_else(|| panic!("no {} on line {}: {:#?}", code, line, diagnostics))
+        };
+
+        let alias = find("alias-resolved", 1);
+        assert_eq!(alias.fixes[0].edit.as_ref().unwrap().new_text, "name");
+        // `phones:` is also written, so a second `phones:` block is not offered
+        assert!(find("shorthand-expanded", 2).fixes.is_empty());
+        find("phone-normalized", 2);
+        let coerced = find("type-coerced", 4);
+        assert_eq!(
+            coerced.fixes[0].edit.as_ref().unwrap().new_text,
+            "\"5559876543\""
+        );
+        let normalized = find("phone-normalized", 4);
+        assert_eq!(
+            normalized.fixes[0].edit.as_ref().unwrap().new_text,
+            "\"+15559876543\""
+        );
+        assert_eq!(
+            find("alias-resolved", 5).fixes[0]
+                .edit
+                .as_ref()
+                .unwrap()
+                .new_text,
+            "mobile"
+        );
+        assert_eq!(
+            find("type-coerced", 5).fixes[0]
+                .edit
+                .as_ref()
+                .unwrap()
+                .new_text,
+            "[mobile]"
+        );
+        let dropped = find("value-dropped", 6);
+        assert_eq!(dropped.fixes[0].edit.as_ref().unwrap().range.start.line, 6);
+        assert_eq!(dropped.fixes[0].edit.as_ref().unwrap().range.end.line, 7);
+
+        let (_, diagnostics) = parser
+            .parse_lenient_with_diagnostics("mobile: \"+1 555 123 4567\"\n", Some("en"))
+            .unwrap();
+        let shorthand = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("shorthand-expanded"))
+            .unwrap();
+        assert!(shorthand.fixes[0]
+            .edit
+            .as_ref()
+            .unwrap()
+            .new_text
+            .starts_with("phones:\n  - number: \"+15551234567\""));
+        find("shorthand-expanded", 8);
+        find("value-dropped", 9);
+
+        // Plain lenient parsing yields the same card
+        assert_eq!(parser.parse_lenient(input, Some("fr")).unwrap(), ycard);
+    }
+
+    #[test]
+    fn test_single_item_fixes() {
+        let parser = Parser::new();
+        let fix_of = |input: &str, code: &str| {
+            let (_, diagnostics) = parser
+                .parse_lenient_with_diagnostics(input, Some("en"))
+                .unwrap();
+            diagnostics
+                .into_iter()
+                .find(|d| d.code.as_deref() == Some(code))
+                .and_then(|d| d.fixes.into_iter().next())
+                .and_then(|fix| fix.edit)
+                .unwrap_or_else(|| panic!("no {} fix for {:?}", code, input))
+        };
+
+        let input = "version: 1\nphones: 555-123-4567 ext 12\n";
+        assert_eq!(
+            fix_of(input, "type-coerced").new_text,
+            "[555-123-4567 ext 12]"
+        );
+        assert_eq!(
+            fix_of(input, "phone-normalized").new_text,
+            "{number: \"+15551234567\", ext: \"12\"}"
+        );
+
+        // A nested mapping becomes the first item of a block list
+        let input = "version: 1\nemails:\n  address: jane@example.com\n  type: [work]\n";
+        let edit = fix_of(input, "type-coerced");
+        assert_eq!((edit.range.start.line, edit.range.end.line), (2, 3));
+        assert_eq!(
+            edit.new_text,
+            "  - address: jane@example.com\n    type: [work]"
+        );
+        let fixed = "version: 1\nemails:\n  - address: jane@example.com\n    type: [work]\n";
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics(fixed, Some("en"))
+            .unwrap();
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(ycard, parser.parse_lenient(input, Some("en")).unwrap());
+    }
+
+    #[test]
+    fn test_typed_shorthands() {
+        let parser = Parser::new();
+        let input = "\
+work_email: jane@corp.example
+Home-Email: [jane@home.example, j@home.example]
+courriel_pro: jean@societe.example
+work_address: 1 Corporate Way, Springfield
+adresse_domicile:
+  components:
+    locality: Paris
+    country: FR
+";
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics(input, Some("fr"))
+            .unwrap();
+
+        let emails = ycard.emails.unwrap();
+        let email_types: Vec<_> = emails.iter().map(|e| e.r#type[0].clone()).collect();
+        assert_eq!(
+            email_types,
+            vec![
+                EmailType::Work,
+                EmailType::Home,
+                EmailType::Home,
+                EmailType::Work
+            ]
+        );
+
+        let addresses = ycard.addresses.unwrap();
+        assert_eq!(addresses[0].r#type, vec![AddressType::Work]);
+        assert_eq!(
+            addresses[0].formatted.as_deref(),
+            Some("1 Corporate Way, Springfield")
+        );
+        assert_eq!(addresses[1].r#type, vec![AddressType::Home]);
+        assert_eq!(
+            addresses[1]
+                .components
+                .as_ref()
+                .unwrap()
+                .locality
+                .as_deref(),
+            Some("Paris")
+        );
+
+        let expanded = diagnostics
+            .iter()
+            .filter(|d| d.code.as_deref() == Some("shorthand-expanded"))
+            .count();
+        assert_eq!(expanded, 5);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.code.as_deref() != Some("value-dropped")));
+    }
+
+    #[test]
+    fn test_merge_duplicates() {
+        let parser = Parser::new();
+        let input = "\
+mobile: \"+1 555 123 4567\"
+phones:
+  - number: \"+15551234567\"
+    type: work
+    preferred: true
+  - \"+1 555 987 6543\"
+telephone: 555-987-6543
+email: jane@example.com
+courriel: jane@EXAMPLE.com
+";
+        let (ycard, diagnostics) = parser
+            .parse_lenient_with_diagnostics(input, Some("fr"))
+            .unwrap();
+
+        let phones = ycard.phones.unwrap();
+        assert_eq!(phones.len(), 2);
+        assert_eq!(phones[0].r#type, vec![PhoneType::Mobile, PhoneType::Work]);
+        assert_eq!(phones[0].preferred, Some(true));
+        assert_eq!(phones[1].number, "+15559876543");
+        assert_eq!(ycard.emails.unwrap().len(), 1);
+
+        let merged: Vec<u32> = diagnostics
+            .iter()
+            .filter(|d| d.code.as_deref() == Some("entry-merged"))
+            .filter_map(|d| Some(d.range.as_ref()?.start.line))
+            .collect();
+        assert_eq!(merged, vec![1, 6, 8]);
+    }
+
+    #[test]
+    fn test_error_locations() {
+        let parser = Parser::new();
+
+        let error = parser
+            .parse_lenient("version: 1\nname: [Jane\nphones: x\n", None)
+            .unwrap_err();
+        assert_eq!(error.code(), "yaml-syntax-error");
+        let diagnostic = error.to_diagnostic();
+        assert_eq!(diagnostic.level, DiagnosticLevel::Error);
+        assert!(diagnostic.range.is_some());
+        assert!(!diagnostic.message.contains(" at line "));
+
+        let error = parser
+            .parse_lenient("version: 1\nphones:\n  - \"555 12\"\n", None)
+            .unwrap_err();
+        assert_eq!(error.code(), "phone-invalid");
+        let range = error.range().unwrap();
+        assert_eq!((range.start.line, range.start.character), (2, 4));
+        assert_eq!((range.end.line, range.end.character), (2, 12));
+
+        let error = parser.parse_lenient("metadata: 3\n", None).unwrap_err();
+        assert_eq!(error.code(), "field-invalid");
+        assert_eq!(error.range().unwrap().start.line, 0);
+    }
+
+    #[test]
+    fn test_partial_parse() {
+        let parser = Parser::new();
+        let input = "\
+name: Jane Doe
+phones:
+  - \"555 12\"
+  - \"+15551234567\"
+metadata: 3
+emails: [jane@example.com]
+";
+        let (ycard, diagnostics) = parser.parse_partial(input, None);
+        assert!(ycard.name.is_some());
+        assert_eq!(ycard.phones.as_ref().map(Vec::len), Some(1));
+        assert!(ycard.metadata.is_none());
+        assert_eq!(ycard.emails.as_ref().map(Vec::len), Some(1));
+
+        let errors: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.level == DiagnosticLevel::Error)
+            .filter_map(|d| Some((d.code.as_deref()?, d.range.as_ref()?.start.line)))
+            .collect();
+        assert_eq!(errors, vec![("phone-invalid", 2), ("field-invalid", 4)]);
+
+        // Lines before a syntax error are still read
+        let (ycard, diagnostics) =
+            parser.parse_partial("name: Jane\nuid: abc\nphones: [\"+1555\"\n", None);
+        assert_eq!(ycard.uid.as_deref(), Some("abc"));
+        assert_eq!(diagnostics[0].code.as_deref(), Some("yaml-syntax-error"));
     }
 }
@@ -0,0 +1,159 @@
+use crate::schema::Phone;
+use phonenumber::{country, Mode};
+
+/// Human-readable phone display styles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhoneDisplayStyle {
+    /// National format for numbers in the locale's region, international otherwise
+    Auto,
+    National,
+    International,
+}
+
+impl PhoneDisplayStyle {
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(PhoneDisplayStyle::Auto),
+            "national" => Some(PhoneDisplayStyle::National),
+            "international" | "intl" => Some(PhoneDisplayStyle::International),
+            _ => None,
+        }
+    }
+}
+
+impl Phone {
+    /// Render this phone for display in the given locale
+    pub fn display(&self, locale: &str) -> String {
+        format_phone(self, locale, PhoneDisplayStyle::Auto)
+    }
+}
+
+/// Format a phone number (and extension) for human display.
+///
+/// Numbers that cannot be parsed are returned unchanged.
+pub fn format_phone(phone: &Phone, locale: &str, style: PhoneDisplayStyle) -> String {
+    let region = region_for_locale(locale);
+    // Only fall back to the locale's region for numbers without a country code
+    let parse_region = if phone.number.starts_with('+') {
+        None
+    } else {
+        region
+    };
+    let mut out = match phonenumber::parse(parse_region, &phone.number) {
+        Ok(parsed) => {
+            let mode = match style {
+                PhoneDisplayStyle::National => Mode::National,
+                PhoneDisplayStyle::International => Mode::International,
+                PhoneDisplayStyle::Auto => {
+                    if region.is_some() && parsed.country().id() == region {
+                        Mode::National
+                    } else {
+                        Mode::International
+                    }
+                }
+            };
+            parsed.format().mode(mode).to_string()
+        }
+        Err(_) => phone.number.clone(),
+    };
+
+    if let Some(ext) = phone.ext.as_deref().filter(|e| !e.is_empty()) {
+        out.push_str(extension_separator(locale));
+        out.push_str(ext);
+    }
+
+    out
+}
+
+/// Resolve the region a locale refers to: an explicit region subtag wins,
+/// otherwise the primary region for the language is used.
+fn region_for_locale(locale: &str) -> Option<country::Id> {
+    let mut subtags = locale.split(['-', '_']);
+    let language = subtags.next().unwrap_or("").to_lowercase();
+
+    for subtag in subtags {
+        if subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+            return subtag.to_uppercase().parse().ok();
+        }
+    }
+
+    let default_region = match language.as_str() {
+        "en" => "US",
+        "fr" => "FR",
+        "es" => "ES",
+        "de" => "DE",
+        "ja" => "JP",
+        "zh" => "CN",
+        "pt" => "BR",
+        "it" => "IT",
+        "ru" => "RU",
+        "ko" => "KR",
+        "ar" => "SA",
+        "hi" => "IN",
+        "th" => "TH",
+        "vi" => "VN",
+        "tr" => "TR",
+        "pl" => "PL",
+        _ => return None,
+    };
+    default_region.parse().ok()
+}
+
+/// Locale-specific text placed between a number and its extension
+fn extension_separator(locale: &str) -> &'static str {
+    let language = locale.split(['-', '_']).next().unwrap_or("").to_lowercase();
+    match language.as_str() {
+        "fr" => " poste ",
+        "es" => " ext. ",
+        "de" => " Durchwahl ",
+        "ja" => " 内線 ",
+        "zh" => " 转 ",
+        "pt" => " ramal ",
+        "it" => " int. ",
+        "pl" => " wewn. ",
+        _ => " ext. ",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated_types::PhoneType;
+
+    fn phone(number: &str, ext: Option<&str>) -> Phone {
+        Phone {
+            number: number.to_string(),
+            r#type: vec![PhoneType::Mobile],
+            ext: ext.map(|e| e.to_string()),
+            preferred: None,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn test_auto_display_by_locale() {
+        let p = phone("+33612345678", None);
+        assert_eq!(p.display("fr"), "06 12 34 56 78");
+        assert_eq!(p.display("en"), "+33 6 12 34 56 78");
+        assert_eq!(p.display("fr-CA"), "+33 6 12 34 56 78");
+    }
+
+    #[test]
+    fn test_extension_style() {
+        let p = phone("+33123456789", Some("12"));
+        assert_eq!(
+            format_phone(&p, "fr", PhoneDisplayStyle::National),
+            "01 23 45 67 89 poste 12"
+        );
+        assert_eq!(
+            format_phone(&p, "en", PhoneDisplayStyle::International),
+            "+33 1 23 45 67 89 ext. 12"
+        );
+    }
+
+    #[test]
+    fn test_unparseable_number_passthrough() {
+        let p = phone("not a number", None);
+        assert_eq!(p.display("en"), "not a number");
+    }
+}
@@ -0,0 +1,191 @@
+//! Inference of `preferred` for phones and emails that leave it unset.
+
+use crate::schema::{Email, Phone, YCard};
+use serde::{Deserialize, Serialize};
+
+/// How the parser picks a preferred entry when none is marked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreferenceInference {
+    /// Leave `preferred` as written
+    #[default]
+    None,
+    /// The first listed entry is preferred
+    FirstListed,
+    /// The first entry with the earliest matching type is preferred
+    /// (`["mobile", "home"]` prefers a mobile over a home number), falling
+    /// back to the first listed entry
+    TypeOrder(Vec<String>),
+}
+
+impl PreferenceInference {
+    /// Parse `none`, `first`, or a comma-separated type order such as `mobile,home`
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim() {
+            "" => None,
+            "none" => Some(Self::None),
+            "first" => Some(Self::FirstListed),
+            order => Some(Self::TypeOrder(
+                order
+                    .split(',')
+                    .map(|t| t.trim().to_lowercase())
+                    .filter(|t| !t.is_empty())
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Index of the entry to mark preferred, if the collection needs one
+    fn choose<T: Preferable>(&self, entries: &[T]) -> Option<usize> {
+        if entries.is_empty() || entries.iter().any(|e| e.preferred().is_some()) {
+            return None;
+        }
+        match self {
+            Self::None => None,
+            Self::FirstListed => Some(0),
+            Self::TypeOrder(order) => order
+                .iter()
+                .find_map(|wanted| {
+                    entries
+                        .iter()
+                        .position(|e| e.type_names().iter().any(|t| t == wanted))
+                })
+                .or(Some(0)),
+        }
+    }
+
+    /// Mark the chosen phone and email preferred. Collections where any entry
+    /// already sets `preferred` (true or false) are left alone. Returns JSON
+    /// pointers of the entries that were marked.
+    pub fn apply(&self, ycard: &mut YCard) -> Vec<String> {
+        let mut marked = Vec::new();
+        if let Some(phones) = &mut ycard.phones {
+            if let Some(i) = self.choose(phones) {
+                phones[i].preferred = Some(true);
+                marked.push(format!("/phones/{}", i));
+            }
+        }
+        if let Some(emails) = &mut ycard.emails {
+            if let Some(i) = self.choose(emails) {
+                emails[i].preferred = Some(true);
+                marked.push(format!("/emails/{}", i));
+            }
+        }
+        marked
+    }
+}
+
+trait Preferable {
+    fn preferred(&self) -> Option<bool>;
+    fn type_names(&self) -> Vec<String>;
+}
+
+/// Canonical serialized names of a type list
+fn names<T: Serialize>(types: &[T]) -> Vec<String> {
+    types
+        .iter()
+        .filter_map(|t| {
+            serde_json::to_value(t)
+                .ok()?
+                .as_str()
+                .map(str::to_lowercase)
+        })
+        .collect()
+}
+
+impl Preferable for Phone {
+    fn preferred(&self) -> Option<bool> {
+        self.preferred
+    }
+
+    fn type_names(&self) -> Vec<String> {
+        names(&self.r#type)
+    }
+}
+
+impl Preferable for Email {
+    fn preferred(&self) -> Option<bool> {
+        self.preferred
+    }
+
+    fn type_names(&self) -> Vec<String> {
+        names(&self.r#type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated_types::{EmailType, PhoneType};
+
+    fn phone(number: &str, t: PhoneType) -> Phone {
+        Phone {
+            number: number.to_string(),
+            r#type: vec![t],
+            ext: None,
+            preferred: None,
+            label: None,
+        }
+    }
+
+    fn card() -> YCard {
+        YCard {
+            phones: Some(vec![
+                phone("+15550000001", PhoneType::Home),
+                phone("+15550000002", PhoneType::Mobile),
+            ]),
+            emails: Some(vec![Email {
+                address: "jane@example.com".to_string(),
+                r#type: vec![EmailType::Work],
+                preferred: None,
+            }]),
+            ..YCard::default()
+        }
+    }
+
+    #[test]
+    fn test_inference_strategies() {
+        let mut ycard = card();
+        assert!(PreferenceInference::None.apply(&mut ycard).is_empty());
+
+        let marked = PreferenceInference::FirstListed.apply(&mut ycard);
+        assert_eq!(marked, vec!["/phones/0", "/emails/0"]);
+
+        let mut ycard = card();
+        let mobile_first = PreferenceInference::from_str_opt("mobile, home").unwrap();
+        assert_eq!(
+            mobile_first.apply(&mut ycard),
+            vec!["/phones/1", "/emails/0"]
+        );
+        assert_eq!(ycard.phones.as_ref().unwrap()[1].preferred, Some(true));
+
+        // An explicit choice, even `false`, is kept
+        let mut ycard = card();
+        ycard.phones.as_mut().unwrap()[0].preferred = Some(false);
+        assert_eq!(
+            PreferenceInference::FirstListed.apply(&mut ycard),
+            vec!["/emails/0"]
+        );
+    }
+
+    #[test]
+    fn test_preferred_accessors() {
+        let mut ycard = card();
+        // No preference: the mobile wins over the home number
+        assert_eq!(ycard.preferred_phone().unwrap().number, "+15550000002");
+        assert_eq!(ycard.preferred_email().unwrap().address, "jane@example.com");
+
+        ycard.phones.as_mut().unwrap()[0].preferred = Some(true);
+        assert_eq!(ycard.preferred_phone().unwrap().number, "+15550000001");
+
+        // Fax and pager numbers are the last resort
+        ycard.phones = Some(vec![
+            phone("+15550000003", PhoneType::Fax),
+            phone("+15550000004", PhoneType::Work),
+        ]);
+        assert_eq!(ycard.preferred_phone().unwrap().number, "+15550000004");
+
+        assert!(YCard::default().preferred_phone().is_none());
+        assert!(YCard::default().preferred_email().is_none());
+    }
+}
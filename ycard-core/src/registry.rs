@@ -0,0 +1,284 @@
+//! Bundled ISO registries: countries (3166-1), subdivisions (3166-2),
+//! languages (639-1) and scripts (15924), plus BCP-47 tag handling.
+
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Deserialize)]
+struct RegistryFile<T> {
+    entries: T,
+}
+
+fn load<T: for<'de> Deserialize<'de>>(json: &str) -> T {
+    serde_json::from_str::<RegistryFile<T>>(json)
+        .expect("bundled registry is valid JSON")
+        .entries
+}
+
+lazy_static! {
+    /// [alpha-2, alpha-3, name, other names...]
+    static ref COUNTRIES: Vec<Vec<String>> = load(include_str!("../data/iso3166-1.json"));
+    /// country alpha-2 -> [[subdivision, name], ...]
+    static ref SUBDIVISIONS: HashMap<String, Vec<(String, String)>> =
+        load(include_str!("../data/iso3166-2.json"));
+    /// [alpha-2, alpha-3, name]
+    static ref LANGUAGES: Vec<(String, String, String)> =
+        load(include_str!("../data/iso639-1.json"));
+    /// [code, name]
+    static ref SCRIPTS: Vec<(String, String)> = load(include_str!("../data/iso15924.json"));
+}
+
+/// Colloquial country names not covered by the ISO short or official names
+const COUNTRY_NICKNAMES: &[(&str, &str)] = &[
+    ("usa", "US"),
+    ("america", "US"),
+    ("united states of america", "US"),
+    ("uk", "GB"),
+    ("great britain", "GB"),
+    ("britain", "GB"),
+    ("england", "GB"),
+    ("scotland", "GB"),
+    ("wales", "GB"),
+    ("russia", "RU"),
+    ("south korea", "KR"),
+    ("korea", "KR"),
+    ("north korea", "KP"),
+    ("vietnam", "VN"),
+    ("iran", "IR"),
+    ("syria", "SY"),
+    ("holland", "NL"),
+    ("czechia", "CZ"),
+    ("czech republic", "CZ"),
+];
+
+/// Is `code` an assigned ISO-3166-1 alpha-2 code (uppercase)?
+pub fn is_country_code(code: &str) -> bool {
+    COUNTRIES.iter().any(|c| c[0] == code)
+}
+
+/// English short name for an alpha-2 code
+pub fn country_name(code: &str) -> Option<&'static str> {
+    COUNTRIES
+        .iter()
+        .find(|c| c[0] == code)
+        .map(|c| c[2].as_str())
+}
+
+/// Map a country name, alpha-3 code or mis-cased alpha-2 code to its alpha-2 code
+pub fn suggest_country_code(input: &str) -> Option<&'static str> {
+    let folded = input.trim().to_lowercase();
+    if folded.is_empty() {
+        return None;
+    }
+
+    if let Some(country) = COUNTRIES.iter().find(|c| {
+        c[0].eq_ignore_ascii_case(&folded)
+            || c[1].eq_ignore_ascii_case(&folded)
+            || c[2..].iter().any(|name| name.to_lowercase() == folded)
+    }) {
+        return Some(country[0].as_str());
+    }
+
+    COUNTRY_NICKNAMES
+        .iter()
+        .find(|(name, _)| *name == folded)
+        .map(|(_, code)| *code)
+}
+
+/// Is `subdivision` (without the country prefix) an ISO-3166-2 code of `country`?
+pub fn is_subdivision_code(country: &str, subdivision: &str) -> bool {
+    SUBDIVISIONS
+        .get(country)
+        .is_some_and(|subs| subs.iter().any(|(code, _)| code == subdivision))
+}
+
+/// Name of an ISO-3166-2 subdivision, in the form used by the registry
+pub fn subdivision_name(country: &str, subdivision: &str) -> Option<&'static str> {
+    SUBDIVISIONS
+        .get(country)?
+        .iter()
+        .find(|(code, _)| code == subdivision)
+        .map(|(_, name)| name.as_str())
+}
+
+/// Is `code` an ISO-639-1 two-letter language code (lowercase)?
+pub fn is_language_code(code: &str) -> bool {
+    LANGUAGES.iter().any(|(alpha2, _, _)| alpha2 == code)
+}
+
+/// Is `code` an ISO-15924 script code (title case)?
+pub fn is_script_code(code: &str) -> bool {
+    SCRIPTS.iter().any(|(c, _)| c == code)
+}
+
+/// Map a script name or mis-cased code to its ISO-15924 code
+pub fn suggest_script_code(input: &str) -> Option<&'static str> {
+    let folded = input.trim().to_lowercase();
+    SCRIPTS
+        .iter()
+        .find(|(code, name)| code.to_lowercase() == folded || name.to_lowercase() == folded)
+        .map(|(code, _)| code.as_str())
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum LanguageTagError {
+    #[error("language tag is empty")]
+    Empty,
+    #[error("malformed subtag {0:?}")]
+    Malformed(String),
+    #[error("unknown language {0:?}")]
+    UnknownLanguage(String),
+    #[error("unknown script {0:?}")]
+    UnknownScript(String),
+    #[error("unknown region {0:?}")]
+    UnknownRegion(String),
+}
+
+/// Validate a BCP-47 language tag and return it in canonical case,
+/// accepting `_` as a separator (`en_us` -> `en-US`).
+pub fn canonicalize_language_tag(tag: &str) -> Result<String, LanguageTagError> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(LanguageTagError::Empty);
+    }
+
+    let subtags: Vec<String> = tag.split(['-', '_']).map(|s| s.to_lowercase()).collect();
+    let is_alpha = |s: &str, min: usize, max: usize| {
+        (min..=max).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphabetic())
+    };
+    let is_alnum = |s: &str, min: usize, max: usize| {
+        (min..=max).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+
+    let mut out: Vec<String> = Vec::with_capacity(subtags.len());
+    let mut iter = subtags.into_iter().peekable();
+
+    // Private-use only tags (x-...)
+    if iter.peek().is_some_and(|s| s == "x") {
+        for subtag in iter {
+            if subtag != "x" && !is_alnum(&subtag, 1, 8) {
+                return Err(LanguageTagError::Malformed(subtag));
+            }
+            out.push(subtag);
+        }
+        return Ok(out.join("-"));
+    }
+
+    let language = iter.next().unwrap_or_default();
+    // 5-8 letter primary subtags are reserved and none are registered
+    if !is_alpha(&language, 2, 3) {
+        return Err(LanguageTagError::Malformed(language));
+    }
+    if language.len() == 2 && !is_language_code(&language) {
+        return Err(LanguageTagError::UnknownLanguage(language));
+    }
+    out.push(language);
+
+    // extlang
+    let mut extlangs = 0;
+    while extlangs < 3 && iter.peek().is_some_and(|s| is_alpha(s, 3, 3)) {
+        out.extend(iter.next());
+        extlangs += 1;
+    }
+
+    if iter.peek().is_some_and(|s| is_alpha(s, 4, 4)) {
+        let script = iter.next().unwrap_or_default();
+        let script = script[..1].to_uppercase() + &script[1..];
+        if !is_script_code(&script) {
+            return Err(LanguageTagError::UnknownScript(script));
+        }
+        out.push(script);
+    }
+
+    if let Some(region) = iter
+        .next_if(|s| is_alpha(s, 2, 2) || (s.len() == 3 && s.chars().all(|c| c.is_ascii_digit())))
+    {
+        let region = region.to_uppercase();
+        if region.len() == 2 && !is_country_code(&region) {
+            return Err(LanguageTagError::UnknownRegion(region));
+        }
+        out.push(region);
+    }
+
+    // variants, extensions and private use
+    while let Some(subtag) = iter.next() {
+        let variant = is_alnum(&subtag, 5, 8)
+            || (subtag.len() == 4 && subtag.starts_with(|c: char| c.is_ascii_digit()));
+        if variant {
+            out.push(subtag);
+        } else if subtag.len() == 1 && subtag.chars().all(|c| c.is_ascii_alphanumeric()) {
+            let private = subtag == "x";
+            out.push(subtag);
+            let mut any = false;
+            while let Some(ext) = iter.next_if(|s| {
+                if private {
+                    is_alnum(s, 1, 8)
+                } else {
+                    is_alnum(s, 2, 8)
+                }
+            }) {
+                out.push(ext);
+                any = true;
+            }
+            if !any {
+                return Err(LanguageTagError::Malformed(out.join("-")));
+            }
+        } else {
+            return Err(LanguageTagError::Malformed(subtag));
+        }
+    }
+
+    Ok(out.join("-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_registry() {
+        assert!(is_country_code("FR"));
+        assert!(!is_country_code("fr"));
+        assert!(!is_country_code("ZZ"));
+        assert_eq!(suggest_country_code("France"), Some("FR"));
+        assert_eq!(suggest_country_code("deu"), Some("DE"));
+        assert_eq!(suggest_country_code("UK"), Some("GB"));
+        assert_eq!(suggest_country_code("Atlantis"), None);
+        assert!(is_subdivision_code("US", "CA"));
+        assert!(is_subdivision_code("JP", "13"));
+        assert!(!is_subdivision_code("FR", "CA"));
+    }
+
+    #[test]
+    fn test_script_registry() {
+        assert!(is_script_code("Latn"));
+        assert_eq!(suggest_script_code("latin"), Some("Latn"));
+        assert_eq!(suggest_script_code("HANI"), Some("Hani"));
+    }
+
+    #[test]
+    fn test_language_tags() {
+        assert_eq!(canonicalize_language_tag("en_US").unwrap(), "en-US");
+        assert_eq!(
+            canonicalize_language_tag("ZH-hans-cn").unwrap(),
+            "zh-Hans-CN"
+        );
+        assert_eq!(canonicalize_language_tag("es-419").unwrap(), "es-419");
+        assert_eq!(
+            canonicalize_language_tag("de-CH-1996-x-phonebk").unwrap(),
+            "de-CH-1996-x-phonebk"
+        );
+        assert_eq!(
+            canonicalize_language_tag("qq-US"),
+            Err(LanguageTagError::UnknownLanguage("qq".to_string()))
+        );
+        assert_eq!(
+            canonicalize_language_tag("en-ZZ"),
+            Err(LanguageTagError::UnknownRegion("ZZ".to_string()))
+        );
+        assert!(canonicalize_language_tag("english").is_err());
+        assert!(canonicalize_language_tag("en--US").is_err());
+    }
+}
@@ -0,0 +1,850 @@
+use crate::address::{rules_for_country, validate_components};
+use crate::confusables;
+use crate::email::parse_email;
+use crate::registry;
+use crate::schema::{AddressComponents, Name, YCard};
+use crate::validator::{
+    CodeFix, Diagnostic, DiagnosticLevel, MessageArgs, Position, Range, TextEdit, ValidationError,
+    ValidationMode,
+};
+
+/// A single validation check.
+///
+/// Rules are registered with a [`Validator`](crate::validator::Validator) under their
+/// `code`. Diagnostics they emit carry either that code or a code sharing its prefix
+/// (the `email` rule emits `email-missing-at`, `email-normalized`, ...), so severity
+/// overrides and disabling can target a whole rule or one of its diagnostics.
+pub trait Rule: Send + Sync {
+    /// Identifier used to register, enable and disable the rule
+    fn code(&self) -> &str;
+
+    /// Whether the rule runs in the given mode
+    fn applies_to(&self, mode: &ValidationMode) -> bool {
+        !matches!(mode, ValidationMode::SchemaOnly)
+    }
+
+    /// Rules that are off unless explicitly enabled
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError>;
+}
+
+/// All rules shipped with the library, in evaluation order
+pub fn builtin_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(VersionMissingRule),
+        Box::new(VersionUnsupportedRule),
+        Box::new(UidMissingRule),
+        Box::new(PhoneFormatRule),
+        Box::new(EmailRule),
+        Box::new(AddressRule),
+        Box::new(CountryRule),
+        Box::new(LocaleRule),
+        Box::new(ScriptRule),
+        Box::new(HomographRule),
+        Box::new(BidiControlRule),
+        Box::new(InvisibleCharRule),
+        Box::new(PreferredMultipleRule),
+        Box::new(EmptyContactRule),
+    ]
+}
+
+pub(crate) fn diagnostic(level: DiagnosticLevel, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        level,
+        message,
+        code: Some(code.to_string()),
+        range: None,
+        fixes: vec![],
+        path: None,
+        message_args: None,
+    }
+}
+
+/// Quick fix replacing the value at JSON pointer `pointer`. The canonical
+/// model carries no source positions, so the fix has no edit until
+/// [`locate`](crate::validator::locate) finds the value in the source.
+pub(crate) fn replacement_fix(pointer: &str, value: &str) -> CodeFix {
+    CodeFix {
+        title: format!("Set {} to {}", pointer.trim_start_matches('/'), value),
+        kind: "quickfix".to_string(),
+        edit: None,
+        path: Some(pointer.to_string()),
+        value: Some(value.to_string()),
+    }
+}
+
+/// `diagnostic` about the value at JSON pointer `pointer`, with a fix
+/// replacing it by each of `values`
+fn at_value<'a>(
+    mut diagnostic: Diagnostic,
+    pointer: String,
+    values: impl IntoIterator<Item = &'a str>,
+) -> Diagnostic {
+    diagnostic.fixes = values
+        .into_iter()
+        .map(|value| replacement_fix(&pointer, value))
+        .collect();
+    diagnostic.path = Some(pointer);
+    diagnostic
+}
+
+pub struct VersionMissingRule;
+
+impl Rule for VersionMissingRule {
+    fn code(&self) -> &str {
+        "version-missing"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        if ycard.version == 0 {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "Version should be specified (defaulting to 1)".to_string(),
+                code: Some("version-missing".to_string()),
+                range: None,
+                fixes: vec![CodeFix {
+                    title: "Add version: 1".to_string(),
+                    kind: "quickfix".to_string(),
+                    edit: Some(TextEdit {
+                        range: Range {
+                            start: Position {
+                                line: 0,
+                                character: 0,
+                            },
+                            end: Position {
+                                line: 0,
+                                character: 0,
+                            },
+                        },
+                        new_text: "version: 1\n".to_string(),
+                    }),
+                    path: None,
+                    value: None,
+                }],
+                path: None,
+                message_args: Some(MessageArgs::new("version-missing", [])),
+            });
+        }
+        Ok(())
+    }
+}
+
+pub struct VersionUnsupportedRule;
+
+impl Rule for VersionUnsupportedRule {
+    fn code(&self) -> &str {
+        "version-unsupported"
+    }
+
+    fn applies_to(&self, mode: &ValidationMode) -> bool {
+        matches!(mode, ValidationMode::SchemaOnly)
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        if ycard.version > 1 {
+            diagnostics.push(
+                diagnostic(
+                    DiagnosticLevel::Error,
+                    "version-unsupported",
+                    format!("Unsupported version: {}", ycard.version),
+                )
+                .with_args([("version", ycard.version.to_string())]),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Off by default; enable for collections where every card must be addressable
+pub struct UidMissingRule;
+
+impl Rule for UidMissingRule {
+    fn code(&self) -> &str {
+        "uid-missing"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        if ycard.uid.as_deref().is_none_or(|u| u.trim().is_empty()) {
+            diagnostics.push(
+                diagnostic(
+                    DiagnosticLevel::Warning,
+                    "uid-missing",
+                    "Card has no uid".to_string(),
+                )
+                .with_args([]),
+            );
+        }
+        Ok(())
+    }
+}
+
+pub struct PhoneFormatRule;
+
+impl Rule for PhoneFormatRule {
+    fn code(&self) -> &str {
+        "phone-format"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        for phone in ycard.phones.iter().flatten() {
+            if !phone.number.starts_with('+') {
+                diagnostics.push(
+                    diagnostic(
+                        DiagnosticLevel::Warning,
+                        "phone-format",
+                        format!("Phone number should be in E.164 format: {}", phone.number),
+                    )
+                    .with_args([("number", phone.number.clone())]),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct EmailRule;
+
+impl Rule for EmailRule {
+    fn code(&self) -> &str {
+        "email"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        for email in ycard.emails.iter().flatten() {
+            match parse_email(&email.address) {
+                Ok(parsed) => {
+                    if parsed.requires_smtputf8 {
+                        diagnostics.push(
+                            diagnostic(
+                                DiagnosticLevel::Info,
+                                "email-smtputf8",
+                                format!(
+                                    "Email address requires SMTPUTF8 support: {}",
+                                    email.address
+                                ),
+                            )
+                            .with_args([("address", email.address.clone())]),
+                        );
+                    }
+                    if parsed.normalized() != email.address {
+                        diagnostics.push(
+                            diagnostic(
+                                DiagnosticLevel::Hint,
+                                "email-normalized",
+                                format!("Email domain can be normalized: {}", parsed.normalized()),
+                            )
+                            .with_args([("address", parsed.normalized())]),
+                        );
+                    }
+                }
+                Err(e) => {
+                    diagnostics.push(
+                        diagnostic(
+                            DiagnosticLevel::Error,
+                            e.code(),
+                            format!("Invalid email address {}: {}", email.address, e),
+                        )
+                        .with_args(
+                            [("address", email.address.clone()), ("error", e.to_string())]
+                                .into_iter()
+                                .chain(e.detail().map(|detail| ("detail", detail))),
+                        ),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Country-specific postal rules
+pub struct AddressRule;
+
+impl Rule for AddressRule {
+    fn code(&self) -> &str {
+        "address"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        for (i, components) in address_components(ycard) {
+            for issue in validate_components(components) {
+                let level = if issue.suggestion.is_some() {
+                    DiagnosticLevel::Hint
+                } else {
+                    DiagnosticLevel::Warning
+                };
+                let pointer = format!("/addresses/{}/components/{}", i, issue.field.as_str());
+                let d = diagnostic(level, issue.code, issue.message).with_args(issue.args);
+                diagnostics.push(at_value(d, pointer, issue.suggestion.as_deref()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ISO-3166-1 countries and ISO-3166-2 subdivisions
+pub struct CountryRule;
+
+impl Rule for CountryRule {
+    fn code(&self) -> &str {
+        "country-invalid"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        for (i, components) in address_components(ycard) {
+            let Some(country) = components.country.as_deref() else {
+                continue;
+            };
+
+            if !registry::is_country_code(country) {
+                let fix = registry::suggest_country_code(country);
+                let message = match fix {
+                    Some(code) => format!(
+                        "Country should be an ISO-3166 alpha-2 code: {} → {}",
+                        country, code
+                    ),
+                    None => format!("Unknown ISO-3166 country code: {}", country),
+                };
+                let mut d = diagnostic(DiagnosticLevel::Warning, "country-invalid", message);
+                d = match fix {
+                    Some(code) => d.with_message_id(
+                        "country-invalid.suggestion",
+                        [
+                            ("country", country.to_string()),
+                            ("suggestion", code.to_string()),
+                        ],
+                    ),
+                    None => d.with_args([("country", country.to_string())]),
+                };
+                let pointer = format!("/addresses/{}/components/country", i);
+                diagnostics.push(at_value(d, pointer, fix));
+                continue;
+            }
+
+            // Regions written as ISO-3166-2 codes (e.g. "FR-75C"); countries with
+            // their own region lists are handled by the address rules
+            let has_region_list = rules_for_country(country).is_some_and(|r| r.regions.is_some());
+            if let Some((prefix, subdivision)) = components
+                .region
+                .as_deref()
+                .and_then(|r| r.split_once('-'))
+                .filter(|(p, _)| p.len() == 2 && p.chars().all(|c| c.is_ascii_alphabetic()))
+            {
+                if !prefix.eq_ignore_ascii_case(country) {
+                    diagnostics.push(
+                        diagnostic(
+                            DiagnosticLevel::Warning,
+                            "country-region-invalid",
+                            format!(
+                                "Region {}-{} does not belong to country {}",
+                                prefix, subdivision, country
+                            ),
+                        )
+                        .with_message_id(
+                            "country-region-invalid.country",
+                            [
+                                ("region", format!("{}-{}", prefix, subdivision)),
+                                ("country", country.to_string()),
+                            ],
+                        ),
+                    );
+                } else if !has_region_list
+                    && !registry::is_subdivision_code(country, &subdivision.to_uppercase())
+                {
+                    diagnostics.push(
+                        diagnostic(
+                            DiagnosticLevel::Warning,
+                            "country-region-invalid",
+                            format!(
+                                "Unknown ISO-3166-2 subdivision for {}: {}-{}",
+                                country, prefix, subdivision
+                            ),
+                        )
+                        .with_args([
+                            ("region", format!("{}-{}", prefix, subdivision)),
+                            ("country", country.to_string()),
+                        ]),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// BCP-47 `metadata.locale`, name languages and spoken languages
+pub struct LocaleRule;
+
+impl Rule for LocaleRule {
+    fn code(&self) -> &str {
+        "locale-invalid"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        let metadata = ycard
+            .metadata
+            .as_ref()
+            .and_then(|m| m.locale.as_deref())
+            .map(|locale| ("/metadata/locale".to_string(), locale));
+        let languages = name_forms(ycard).into_iter().filter_map(|(prefix, name)| {
+            let language = name.language.as_deref()?;
+            Some((format!("{}/language", prefix), language))
+        });
+        let spoken = ycard
+            .languages
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, language)| (format!("/languages/{}/tag", i), language.tag.as_str()));
+        for (field, locale) in metadata.into_iter().chain(languages).chain(spoken) {
+            Self::check_tag(&field, locale, diagnostics);
+        }
+        Ok(())
+    }
+}
+
+impl LocaleRule {
+    fn check_tag(pointer: &str, locale: &str, diagnostics: &mut Vec<Diagnostic>) {
+        match registry::canonicalize_language_tag(locale) {
+            Ok(canonical) if canonical == locale => {}
+            Ok(canonical) => {
+                let d = diagnostic(
+                    DiagnosticLevel::Warning,
+                    "locale-invalid",
+                    format!("Locale should be a BCP-47 tag: {} → {}", locale, canonical),
+                )
+                .with_message_id(
+                    "locale-invalid.canonical",
+                    [
+                        ("locale", locale.to_string()),
+                        ("canonical", canonical.clone()),
+                    ],
+                );
+                diagnostics.push(at_value(d, pointer.to_string(), [canonical.as_str()]));
+            }
+            Err(e) => diagnostics.push(
+                diagnostic(
+                    DiagnosticLevel::Warning,
+                    "locale-invalid",
+                    format!("Invalid BCP-47 locale {}: {}", locale, e),
+                )
+                .with_args([("locale", locale.to_string()), ("error", e.to_string())]),
+            ),
+        }
+    }
+}
+
+/// ISO-15924 `name.script`
+pub struct ScriptRule;
+
+impl Rule for ScriptRule {
+    fn code(&self) -> &str {
+        "script-invalid"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        for (prefix, name) in name_forms(ycard) {
+            let Some(script) = name.script.as_deref() else {
+                continue;
+            };
+            if registry::is_script_code(script) {
+                continue;
+            }
+
+            let fix = registry::suggest_script_code(script);
+            let message = match fix {
+                Some(code) => format!("Script should be an ISO-15924 code: {} → {}", script, code),
+                None => format!("Unknown ISO-15924 script code: {}", script),
+            };
+            let mut d = diagnostic(DiagnosticLevel::Warning, "script-invalid", message);
+            d = match fix {
+                Some(code) => d.with_message_id(
+                    "script-invalid.suggestion",
+                    [
+                        ("script", script.to_string()),
+                        ("suggestion", code.to_string()),
+                    ],
+                ),
+                None => d.with_args([("script", script.to_string())]),
+            };
+            diagnostics.push(at_value(d, format!("{}/script", prefix), fix));
+        }
+        Ok(())
+    }
+}
+
+/// Each form of the name with its JSON pointer: `/name` for the name itself,
+/// `/name/alternates/0` for the first alternate
+fn name_forms(ycard: &YCard) -> Vec<(String, &Name)> {
+    let Some(name) = &ycard.name else {
+        return vec![];
+    };
+    let alternates = name
+        .alternates
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, alternate)| (format!("/name/alternates/{}", i), alternate));
+    std::iter::once(("/name".to_string(), name))
+        .chain(alternates)
+        .collect()
+}
+
+/// Address components with the index of their address
+fn address_components(ycard: &YCard) -> impl Iterator<Item = (usize, &AddressComponents)> {
+    ycard
+        .addresses
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(i, a)| Some((i, a.components.as_ref()?)))
+}
+
+/// Name fields of every form and nicknames as (field, JSON pointer, text)
+fn name_texts(ycard: &YCard) -> Vec<(String, String, &str)> {
+    let mut texts = Vec::new();
+    for (pointer, name) in name_forms(ycard) {
+        // `alternates/0/` for the first alternate, as messages name the field
+        let prefix = match pointer.strip_prefix("/name/") {
+            Some(alternate) => format!("{}/", alternate),
+            None => String::new(),
+        };
+        for (field, text) in [
+            ("displayName", &name.display_name),
+            ("phoneticName", &name.phonetic_name),
+        ] {
+            if let Some(text) = text {
+                texts.push((
+                    format!("{}{}", prefix, field),
+                    format!("{}/{}", pointer, field),
+                    text.as_str(),
+                ));
+            }
+        }
+        for (field, parts) in [
+            ("givenName", &name.given_name),
+            ("middleName", &name.middle_name),
+            ("familyName", &name.family_name),
+            ("honorificPrefix", &name.honorific_prefix),
+            ("honorificSuffix", &name.honorific_suffix),
+            ("phoneticGivenName", &name.phonetic_given_name),
+            ("phoneticFamilyName", &name.phonetic_family_name),
+        ] {
+            for (j, part) in parts.iter().flatten().enumerate() {
+                texts.push((
+                    format!("{}{}", prefix, field),
+                    format!("{}/{}/{}", pointer, field, j),
+                    part.as_str(),
+                ));
+            }
+        }
+    }
+    for (j, nickname) in ycard.nickname.iter().flatten().enumerate() {
+        texts.push((
+            "nickname".to_string(),
+            format!("/nickname/{}", j),
+            nickname.as_str(),
+        ));
+    }
+    texts
+}
+
+/// UTS #39 mixed-script and whole-script confusables in names and email addresses
+pub struct HomographRule;
+
+impl Rule for HomographRule {
+    fn code(&self) -> &str {
+        "homograph"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        let mixed = |field: &str, word: &str, diagnostics: &mut Vec<Diagnostic>| {
+            if let Some(scripts) = confusables::mixed_scripts(word) {
+                diagnostics.push(
+                    diagnostic(
+                        DiagnosticLevel::Warning,
+                        "homograph-mixed-script",
+                        format!(
+                            "{} \"{}\" mixes {} scripts",
+                            field,
+                            word,
+                            scripts.join(" and ")
+                        ),
+                    )
+                    .with_args([
+                        ("field", field.to_string()),
+                        ("word", word.to_string()),
+                        ("scripts", scripts.join(", ")),
+                    ]),
+                );
+            }
+        };
+
+        for (field, _, text) in name_texts(ycard) {
+            for word in text.split(|c: char| !c.is_alphanumeric()) {
+                mixed(&field, word, diagnostics);
+            }
+        }
+
+        for email in ycard.emails.iter().flatten() {
+            let Ok(parsed) = parse_email(&email.address) else {
+                continue;
+            };
+            mixed("Email local part", &parsed.local, diagnostics);
+            for label in parsed.domain.split('.') {
+                mixed("Email domain label", label, diagnostics);
+                if let Some(lookalike) = confusables::ascii_lookalike(label) {
+                    diagnostics.push(
+                        diagnostic(
+                            DiagnosticLevel::Warning,
+                            "homograph-confusable",
+                            format!(
+                                "Email domain label \"{}\" is confusable with \"{}\" in {}",
+                                label, lookalike, email.address
+                            ),
+                        )
+                        .with_args([
+                            ("label", label.to_string()),
+                            ("lookalike", lookalike.to_string()),
+                            ("address", email.address.clone()),
+                        ]),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Bidi formatting characters that can reorder how a value is displayed
+pub struct BidiControlRule;
+
+impl Rule for BidiControlRule {
+    fn code(&self) -> &str {
+        "bidi-control"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        let emails = ycard.emails.iter().flatten().enumerate().map(|(i, e)| {
+            let pointer = format!("/emails/{}/address", i);
+            ("address".to_string(), pointer, e.address.as_str())
+        });
+        for (field, pointer, text) in name_texts(ycard).into_iter().chain(emails) {
+            if text.chars().any(confusables::is_bidi_control) {
+                let d = diagnostic(
+                    DiagnosticLevel::Warning,
+                    "bidi-control",
+                    format!("{} contains bidi control characters: {:?}", field, text),
+                )
+                .with_args([("field", field.clone()), ("text", format!("{:?}", text))]);
+                let stripped = confusables::strip_bidi(text);
+                diagnostics.push(at_value(d, pointer, [stripped.as_str()]));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Zero-width and other invisible characters in names
+pub struct InvisibleCharRule;
+
+impl Rule for InvisibleCharRule {
+    fn code(&self) -> &str {
+        "invisible-char"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        for (field, pointer, text) in name_texts(ycard) {
+            let hidden = confusables::invisible_chars(text);
+            if let Some(c) = hidden.first() {
+                let d = diagnostic(
+                    DiagnosticLevel::Warning,
+                    "invisible-char",
+                    format!(
+                        "{} contains invisible character U+{:04X}: {:?}",
+                        field, *c as u32, text
+                    ),
+                )
+                .with_args([
+                    ("field", field.to_string()),
+                    ("codepoint", format!("U+{:04X}", *c as u32)),
+                    ("text", format!("{:?}", text)),
+                ]);
+                let stripped = confusables::strip_invisible(text);
+                diagnostics.push(at_value(d, pointer, [stripped.as_str()]));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// At most one phone, one email and one language may be marked preferred
+pub struct PreferredMultipleRule;
+
+impl PreferredMultipleRule {
+    fn check_collection<'a>(
+        field: &str,
+        entries: impl Iterator<Item = (&'a str, Option<bool>)>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let preferred: Vec<(usize, &str)> = entries
+            .enumerate()
+            .filter(|(_, (_, preferred))| *preferred == Some(true))
+            .map(|(i, (value, _))| (i, value))
+            .collect();
+        if preferred.len() < 2 {
+            return;
+        }
+        let values: Vec<&str> = preferred.iter().map(|(_, value)| *value).collect();
+        for (i, _) in &preferred[1..] {
+            let mut diagnostic = diagnostic(
+                DiagnosticLevel::Warning,
+                "preferred-multiple",
+                format!(
+                    "{} {} are marked preferred ({}); only the first counts",
+                    preferred.len(),
+                    field,
+                    values.join(", ")
+                ),
+            )
+            .with_args([
+                ("count", preferred.len().to_string()),
+                ("field", field.to_string()),
+                ("values", values.join(", ")),
+            ]);
+            diagnostic.path = Some(format!("/{}/{}/preferred", field, i));
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+impl Rule for PreferredMultipleRule {
+    fn code(&self) -> &str {
+        "preferred-multiple"
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        Self::check_collection(
+            "phones",
+            ycard
+                .phones
+                .iter()
+                .flatten()
+                .map(|p| (p.number.as_str(), p.preferred)),
+            diagnostics,
+        );
+        Self::check_collection(
+            "emails",
+            ycard
+                .emails
+                .iter()
+                .flatten()
+                .map(|e| (e.address.as_str(), e.preferred)),
+            diagnostics,
+        );
+        Self::check_collection(
+            "languages",
+            ycard
+                .languages
+                .iter()
+                .flatten()
+                .map(|l| (l.tag.as_str(), l.preferred)),
+            diagnostics,
+        );
+        Ok(())
+    }
+}
+
+/// Strict mode only: a card must identify someone
+pub struct EmptyContactRule;
+
+impl Rule for EmptyContactRule {
+    fn code(&self) -> &str {
+        "empty-contact"
+    }
+
+    fn applies_to(&self, mode: &ValidationMode) -> bool {
+        matches!(mode, ValidationMode::Strict)
+    }
+
+    fn check(
+        &self,
+        ycard: &YCard,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), ValidationError> {
+        if ycard.name.is_none() && ycard.phones.is_none() && ycard.emails.is_none() {
+            diagnostics.push(
+                diagnostic(
+                    DiagnosticLevel::Error,
+                    "empty-contact",
+                    "At least one of name, phones, or emails must be present".to_string(),
+                )
+                .with_args([]),
+            );
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
-use crate::generated_types::{AddressType, EmailType, PhoneType};
+use crate::collation::SortKey;
+use crate::generated_types::{AddressType, EmailType, PhoneType, Sex};
+use crate::locale::LanguageId;
+use crate::text::is_unspaced_script;
 use serde::{Deserialize, Serialize};
+use unicode_script::{Script, UnicodeScript};
 
 /// yCard canonical schema types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -7,14 +11,22 @@ pub struct YCard {
     pub version: u8,
     pub uid: Option<String>,
     pub name: Option<Name>,
+    #[serde(default)]
+    pub nickname: Option<Vec<String>>,
+    #[serde(default)]
+    pub pronouns: Option<String>,
+    #[serde(default)]
+    pub gender: Option<Gender>,
     pub phones: Option<Vec<Phone>>,
     pub emails: Option<Vec<Email>>,
     pub addresses: Option<Vec<Address>>,
     pub manager: Option<String>,
+    #[serde(default)]
+    pub languages: Option<Vec<Language>>,
     pub metadata: Option<Metadata>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct Name {
     #[serde(rename = "givenName")]
     pub given_name: Option<Vec<String>>,
@@ -29,6 +41,17 @@ pub struct Name {
     #[serde(rename = "displayName")]
     pub display_name: Option<String>,
     pub script: Option<String>,
+    #[serde(default, rename = "phoneticGivenName")]
+    pub phonetic_given_name: Option<Vec<String>>,
+    #[serde(default, rename = "phoneticFamilyName")]
+    pub phonetic_family_name: Option<Vec<String>>,
+    #[serde(default, rename = "phoneticName")]
+    pub phonetic_name: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>, // BCP-47
+    /// The same name in other scripts or languages, like vCard ALTID
+    #[serde(default)]
+    pub alternates: Option<Vec<Name>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -70,6 +93,19 @@ pub struct AddressComponents {
     pub country: Option<String>, // ISO-3166 alpha-2
 }
 
+/// Gender with vCard GENDER semantics: a sex component and free-text identity
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Gender {
+    pub sex: Option<Sex>,
+    pub identity: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Language {
+    pub tag: String, // BCP-47
+    pub preferred: Option<bool>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Metadata {
     pub locale: Option<String>, // BCP-47
@@ -82,13 +118,222 @@ impl Default for YCard {
             version: 1,
             uid: None,
             name: None,
+            nickname: None,
+            pronouns: None,
+            gender: None,
             phones: None,
             emails: None,
             addresses: None,
             manager: None,
+            languages: None,
             metadata: None,
         }
     }
 }
 
+impl YCard {
+    /// The phone marked preferred, else the first mobile, else the first
+    /// number that takes calls (not fax or pager), else the first listed
+    pub fn preferred_phone(&self) -> Option<&Phone> {
+        let phones = self.phones.as_deref().unwrap_or_default();
+        phones
+            .iter()
+            .find(|p| p.preferred == Some(true))
+            .or_else(|| {
+                phones
+                    .iter()
+                    .find(|p| p.r#type.contains(&PhoneType::Mobile))
+            })
+            .or_else(|| {
+                phones.iter().find(|p| {
+                    !p.r#type
+                        .iter()
+                        .any(|t| matches!(t, PhoneType::Fax | PhoneType::Pager))
+                })
+            })
+            .or_else(|| phones.first())
+    }
+
+    /// The email marked preferred, else the first listed
+    pub fn preferred_email(&self) -> Option<&Email> {
+        let emails = self.emails.as_deref().unwrap_or_default();
+        emails
+            .iter()
+            .find(|e| e.preferred == Some(true))
+            .or_else(|| emails.first())
+    }
+
+    /// The language marked preferred, else the first listed
+    pub fn preferred_language(&self) -> Option<&Language> {
+        let languages = self.languages.as_deref().unwrap_or_default();
+        languages
+            .iter()
+            .find(|l| l.preferred == Some(true))
+            .or_else(|| languages.first())
+    }
+
+    /// The text the card sorts under for readers of `locale`: the reading of
+    /// the name form chosen for them, else its family names before its given
+    /// names, else its display name, else the first nickname
+    pub fn sort_name(&self, locale: &str) -> Option<String> {
+        fn words(parts: &[&Option<Vec<String>>]) -> Option<String> {
+            let words: Vec<&str> = parts
+                .iter()
+                .copied()
+                .flatten()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            (!words.is_empty()).then(|| words.join(" "))
+        }
+        let nickname = || self.nickname.iter().flatten().next().cloned();
+        let Some(name) = &self.name else {
+            return nickname();
+        };
+        let form = name.for_locale(locale);
+        words(&[&form.phonetic_family_name, &form.phonetic_given_name])
+            .or_else(|| form.phonetic_name.clone())
+            .or_else(|| words(&[&form.family_name, &form.given_name, &form.middle_name]))
+            .or_else(|| form.display_name.clone())
+            .or_else(nickname)
+    }
+
+    /// The collation key of [`YCard::sort_name`] for `locale`
+    pub fn sort_key(&self, locale: &str) -> Option<SortKey> {
+        self.sort_name(locale)
+            .map(|text| SortKey::new(&text, locale))
+    }
+}
+
+impl Name {
+    /// This form of the name followed by its alternates
+    pub fn forms(&self) -> impl Iterator<Item = &Name> {
+        std::iter::once(self).chain(self.alternates.iter().flatten())
+    }
+
+    /// The form to show a reader of `locale`: one in the reader's script,
+    /// preferring their language, else one in their language, else this one
+    pub fn for_locale(&self, locale: &str) -> &Name {
+        let Ok(reader) = LanguageId::parse(locale).map(|id| id.maximize()) else {
+            return self;
+        };
+        let mut best = self;
+        let mut best_score = form_score(self, &reader);
+        for form in self.alternates.iter().flatten() {
+            let score = form_score(form, &reader);
+            if score > best_score {
+                best = form;
+                best_score = score;
+            }
+        }
+        best
+    }
+
+    /// The name with the form for `locale` first and every other form,
+    /// this one included, as its alternates
+    pub fn localized(&self, locale: &str) -> Name {
+        let chosen = self.for_locale(locale);
+        if std::ptr::eq(chosen, self) {
+            return self.clone();
+        }
+        let mut primary = chosen.clone();
+        let others = self
+            .forms()
+            .filter(|form| !std::ptr::eq(*form, chosen))
+            .map(|form| Name {
+                alternates: None,
+                ..form.clone()
+            });
+        primary.alternates = Some(others.collect());
+        primary
+    }
+
+    /// `displayName`, else the parts joined in the order the name's
+    /// language or script writes them: `山田花子`, `Wang Xiaoming`,
+    /// `Dr. Jane Q. Doe`
+    pub fn display(&self) -> Option<String> {
+        if let Some(display) = &self.display_name {
+            return Some(display.clone());
+        }
+        fn parts(field: &Option<Vec<String>>) -> impl Iterator<Item = String> + '_ {
+            field.iter().flatten().cloned()
+        }
+        let family: Vec<String> = parts(&self.family_name).collect();
+        let given: Vec<String> = parts(&self.given_name).collect();
+        let written = family.iter().chain(&given).cloned().collect::<String>();
+        let language = self
+            .language
+            .as_deref()
+            .and_then(|tag| LanguageId::parse(tag).ok())
+            .map(|id| id.language);
+        let family_first = matches!(
+            language.as_deref(),
+            Some("zh" | "ja" | "ko" | "vi" | "hu" | "mn")
+        );
+
+        let words: Vec<String> = if is_unspaced_script(&written) {
+            vec![family.concat() + &given.concat()]
+        } else if family_first {
+            parts(&self.honorific_prefix)
+                .chain(family)
+                .chain(given)
+                .chain(parts(&self.middle_name))
+                .chain(parts(&self.honorific_suffix))
+                .collect()
+        } else {
+            parts(&self.honorific_prefix)
+                .chain(given)
+                .chain(parts(&self.middle_name))
+                .chain(family)
+                .chain(parts(&self.honorific_suffix))
+                .collect()
+        };
+        let display = words.join(" ");
+        (!display.is_empty()).then_some(display)
+    }
+}
+
+/// How well a form of a name suits a reader: 2 for the reader's script,
+/// 1 for their language
+fn form_score(form: &Name, reader: &LanguageId) -> u8 {
+    let language = form
+        .language
+        .as_deref()
+        .and_then(|tag| LanguageId::parse(tag).ok())
+        .map(|id| id.maximize());
+    let script = form
+        .script
+        .clone()
+        .or_else(|| language.as_ref().and_then(|id| id.script.clone()))
+        .or_else(|| written_script(form));
+
+    let same_language = language.is_some_and(|id| id.language == reader.language);
+    let same_script = match (script, &reader.script) {
+        (Some(script), Some(reader_script)) => writes_in(reader_script, &script),
+        _ => false,
+    };
+    2 * same_script as u8 + same_language as u8
+}
+
+/// The ISO-15924 code of the first letter of the name
+fn written_script(form: &Name) -> Option<String> {
+    let text = form.display().unwrap_or_default();
+    text.chars()
+        .map(|c| c.script())
+        .find(|s| !matches!(s, Script::Common | Script::Inherited | Script::Unknown))
+        .map(|s| s.short_name().to_string())
+}
+
+/// Whether text in `script` is legible to readers of `reader_script`, which
+/// may be a union like `Jpan` (Han and kana) or `Kore` (Han and Hangul)
+fn writes_in(reader_script: &str, script: &str) -> bool {
+    let union: &[&str] = match reader_script {
+        "Jpan" => &["Hani", "Hira", "Kana", "Hrkt"],
+        "Kore" => &["Hani", "Hang"],
+        "Hans" | "Hant" => &["Hani"],
+        _ => &[],
+    };
+    script == reader_script || union.contains(&script)
+}
+
 // from_str_with_locale implementations now in generated_types
@@ -0,0 +1,481 @@
+//! Source positions for block-style YAML.
+//!
+//! serde_yaml values carry no locations, so diagnostics locate nodes by
+//! re-scanning the text: each mapping key and sequence item is recorded
+//! under its path from the document root. Flow collections (`[a, b]`) are
+//! split one level deep, which covers the shapes yCard documents use.
+
+use crate::validator::{Position, Range};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl From<&str> for PathSegment {
+    fn from(key: &str) -> Self {
+        PathSegment::Key(key.to_string())
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        PathSegment::Index(index)
+    }
+}
+
+/// Location of one node
+#[derive(Debug, Clone)]
+pub struct SourceEntry {
+    /// The key (for mapping entries) or the `-` marker (for block sequence items)
+    pub key: Range,
+    /// The inline value, or an empty range after the key when the value is a nested block
+    pub value: Range,
+    /// The key and its value, including nested block lines
+    pub full: Range,
+    /// The entry begins its line, so deleting `full` line-wise is safe
+    pub starts_line: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FrameKind {
+    Map,
+    Seq,
+}
+
+struct Frame {
+    indent: usize,
+    path: Vec<PathSegment>,
+    kind: FrameKind,
+    next_index: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    /// Each node's location and the column its block starts at
+    entries: HashMap<Vec<PathSegment>, (SourceEntry, usize)>,
+}
+
+impl SourceMap {
+    pub fn new(input: &str) -> Self {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut map = SourceMap::default();
+        let mut frames = vec![Frame {
+            indent: 0,
+            path: vec![],
+            kind: FrameKind::Map,
+            next_index: 0,
+        }];
+        // Key whose value is a nested block that has not started yet: (path, column)
+        let mut pending: Option<(Vec<PathSegment>, usize)> = None;
+
+        for (line_no, line) in lines.iter().enumerate() {
+            let content = strip_comment(line);
+            let trimmed = content.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with("---") || trimmed.starts_with("...") {
+                continue;
+            }
+            let indent = content.len() - trimmed.len();
+
+            if let Some((path, column)) = pending.take() {
+                let is_dash = is_seq_item(trimmed);
+                if indent > column || (indent == column && is_dash) {
+                    frames.push(Frame {
+                        indent,
+                        path,
+                        kind: if is_dash {
+                            FrameKind::Seq
+                        } else {
+                            FrameKind::Map
+                        },
+                        next_index: 0,
+                    });
+                }
+            }
+
+            map.scan_node(
+                line,
+                line_no,
+                indent,
+                content.trim_end(),
+                &mut frames,
+                &mut pending,
+            );
+        }
+
+        map.compute_extents(&lines);
+        map
+    }
+
+    /// Location of the node at `path`
+    pub fn get(&self, path: &[PathSegment]) -> Option<&SourceEntry> {
+        self.entries.get(path).map(|(entry, _)| entry)
+    }
+
+    fn scan_node(
+        &mut self,
+        line: &str,
+        line_no: usize,
+        column: usize,
+        content: &str,
+        frames: &mut Vec<Frame>,
+        pending: &mut Option<(Vec<PathSegment>, usize)>,
+    ) {
+        let text = &content[column..];
+
+        if is_seq_item(text) {
+            while frames.len() > 1
+                && frames.last().is_some_and(|f| {
+                    f.indent > column || (f.indent == column && f.kind != FrameKind::Seq)
+                })
+            {
+                frames.pop();
+            }
+            let Some(frame) = frames.last_mut().filter(|f| f.kind == FrameKind::Seq) else {
+                return;
+            };
+            let mut path = frame.path.clone();
+            path.push(PathSegment::Index(frame.next_index));
+            frame.next_index += 1;
+
+            let rest = text[1..].trim_start();
+            let rest_column = content.len() - rest.len();
+            self.push(
+                path.clone(),
+                SourceEntry {
+                    key: span(line, line_no, column, column + 1),
+                    value: span(line, line_no, rest_column, content.len()),
+                    full: span(line, line_no, column, content.len()),
+                    starts_line: true,
+                },
+                column,
+            );
+
+            if rest.is_empty() {
+                *pending = Some((path, column));
+            } else if split_key(rest).is_some() {
+                // `- key: value` opens a mapping whose keys align with `key`
+                frames.push(Frame {
+                    indent: rest_column,
+                    path,
+                    kind: FrameKind::Map,
+                    next_index: 0,
+                });
+                self.scan_node(line, line_no, rest_column, content, frames, pending);
+            } else {
+                self.scan_flow(line, line_no, &path, rest_column, content.len());
+            }
+            return;
+        }
+
+        let Some((key, value_offset)) = split_key(text) else {
+            return;
+        };
+        while frames.len() > 1
+            && frames.last().is_some_and(|f| {
+                f.indent > column || (f.indent == column && f.kind != FrameKind::Map)
+            })
+        {
+            frames.pop();
+        }
+        let Some(frame) = frames.last() else {
+            return;
+        };
+        let mut path = frame.path.clone();
+        path.push(PathSegment::Key(key.clone()));
+
+        let raw_key_len = value_offset - 1;
+        let value_start = column + value_offset;
+        let value_text = &content[value_start..];
+        let value_column = value_start + (value_text.len() - value_text.trim_start().len());
+        self.push(
+            path.clone(),
+            SourceEntry {
+                key: span(line, line_no, column, column + raw_key_len),
+                value: span(line, line_no, value_column, content.len()),
+                full: span(line, line_no, column, content.len()),
+                starts_line: line[..column].trim().is_empty(),
+            },
+            column,
+        );
+
+        if value_column >= content.len() {
+            *pending = Some((path, column));
+        } else {
+            self.scan_flow(line, line_no, &path, value_column, content.len());
+        }
+    }
+
+    /// Record the items of a one-line flow sequence
+    fn scan_flow(
+        &mut self,
+        line: &str,
+        line_no: usize,
+        path: &[PathSegment],
+        start: usize,
+        end: usize,
+    ) {
+        let text = &line[start..end];
+        let Some(inner) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) else {
+            return;
+        };
+
+        let mut offset = start + 1;
+        for (index, item) in split_flow(inner).into_iter().enumerate() {
+            let item_start = offset + (item.len() - item.trim_start().len());
+            let item_end = offset + item.trim_end().len();
+            let mut item_path = path.to_vec();
+            item_path.push(PathSegment::Index(index));
+            let range = span(line, line_no, item_start, item_end);
+            self.push(
+                item_path,
+                SourceEntry {
+                    key: range.clone(),
+                    value: range.clone(),
+                    full: range,
+                    starts_line: false,
+                },
+                usize::MAX,
+            );
+            offset += item.len() + 1;
+        }
+    }
+
+    fn push(&mut self, path: Vec<PathSegment>, entry: SourceEntry, column: usize) {
+        // First occurrence wins, matching how duplicate keys are reported
+        self.entries.entry(path).or_insert((entry, column));
+    }
+
+    /// Extend block entries over the indented lines that belong to them
+    fn compute_extents(&mut self, lines: &[&str]) {
+        for (entry, column) in self.entries.values_mut() {
+            if *column == usize::MAX {
+                continue;
+            }
+            let start_line = entry.full.start.line as usize;
+            let mut last = start_line;
+            let mut in_sequence = false;
+            for (i, line) in lines.iter().enumerate().skip(start_line + 1) {
+                let content = strip_comment(line);
+                let trimmed = content.trim_start();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                let indent = content.len() - trimmed.len();
+                // A block sequence may sit at the same indent as its key
+                let same_level_item = indent == *column
+                    && is_seq_item(trimmed)
+                    && (in_sequence
+                        || (last == start_line
+                            && entry.value.start.character == entry.value.end.character));
+                if indent > *column || same_level_item {
+                    in_sequence |= same_level_item;
+                    last = i;
+                } else {
+                    break;
+                }
+            }
+            if last != start_line {
+                let text = strip_comment(lines[last]).trim_end();
+                entry.full.end = span(lines[last], last, text.len(), text.len()).end;
+            }
+        }
+    }
+}
+
+fn is_seq_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Split `key: value` (or `key:`), returning the unquoted key and the byte offset of the value
+fn split_key(text: &str) -> Option<(String, usize)> {
+    let (key, rest_start) =
+        if let Some(quote) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let close = text[1..].find(quote)? + 1;
+            (text[1..close].to_string(), close + 1)
+        } else {
+            let colon = text
+                .char_indices()
+                .find(|&(i, c)| c == ':' && text[i + 1..].chars().next().is_none_or(|n| n == ' '))?
+                .0;
+            let key = &text[..colon];
+            if key.starts_with(['[', '{', '"', '\'']) {
+                return None;
+            }
+            (key.trim_end().to_string(), colon)
+        };
+
+    let rest = &text[rest_start..];
+    let rest = rest.strip_prefix(':')?;
+    if !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some((key, text.len() - rest.len()))
+}
+
+/// Split the inside of a flow sequence at top-level commas
+fn split_flow(inner: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !inner[start..].trim().is_empty() {
+        items.push(&inner[start..]);
+    }
+    items
+}
+
+/// Drop a trailing `# comment` that is outside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') if i == 0 || line[..i].ends_with([' ', '\t']) => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// The JSON pointer of a path, the inverse of [`pointer_path`]
+pub fn path_pointer(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+            PathSegment::Index(index) => format!("/{}", index),
+        })
+        .collect()
+}
+
+/// The path of a JSON pointer (`/phones/0/number`)
+pub fn pointer_path(pointer: &str) -> Option<Vec<PathSegment>> {
+    let rest = pointer.strip_prefix('/')?;
+    Some(
+        rest.split('/')
+            .map(|segment| match segment.parse::<usize>() {
+                Ok(index) => PathSegment::Index(index),
+                Err(_) => PathSegment::Key(segment.replace("~1", "/").replace("~0", "~")),
+            })
+            .collect(),
+    )
+}
+
+/// The text of a one-line `range` of `input`
+pub fn slice<'a>(input: &'a str, range: &Range) -> Option<&'a str> {
+    if range.start.line != range.end.line {
+        return None;
+    }
+    let line = input.lines().nth(range.start.line as usize)?;
+    // Columns count UTF-16 code units
+    let byte = |column: u32| {
+        let mut units = 0;
+        for (i, c) in line.char_indices() {
+            if units >= column as usize {
+                return Some(i);
+            }
+            units += c.len_utf16();
+        }
+        (units >= column as usize).then_some(line.len())
+    };
+    line.get(byte(range.start.character)?..byte(range.end.character)?)
+}
+
+/// Range on one line from byte offsets, in UTF-16 code units as LSP expects
+fn span(line: &str, line_no: usize, start: usize, end: usize) -> Range {
+    let column = |byte: usize| line[..byte.min(line.len())].encode_utf16().count() as u32;
+    Range {
+        start: Position {
+            line: line_no as u32,
+            character: column(start),
+        },
+        end: Position {
+            line: line_no as u32,
+            character: column(end),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(segments: &[PathSegment]) -> Vec<PathSegment> {
+        segments.to_vec()
+    }
+
+    fn at(map: &SourceMap, p: &[PathSegment]) -> (u32, u32, u32) {
+        let entry = map
+            .get(&path(p))
+            .unwrap_or_else(|| panic!("{:?} not found", p));
+        (
+            entry.value.start.line,
+            entry.value.start.character,
+            entry.value.end.character,
+        )
+    }
+
+    #[test]
+    fn test_block_yaml_positions() {
+        let input = "\
+version: 1 # comment
+nom: \"Dupont\"
+phones:
+  - number: 555 1234
+    type: [cell, home]
+  - \"+33 1 23\"
+emails:
+- a@b.c
+name:
+  givenName: Jean
+";
+        let map = SourceMap::new(input);
+        assert_eq!(at(&map, &["version".into()]), (0, 9, 10));
+        assert_eq!(at(&map, &["nom".into()]), (1, 5, 13));
+        assert_eq!(
+            at(&map, &["phones".into(), 0.into(), "number".into()]),
+            (3, 12, 20)
+        );
+        assert_eq!(
+            at(&map, &["phones".into(), 0.into(), "type".into(), 1.into()]),
+            (4, 17, 21)
+        );
+        assert_eq!(at(&map, &["phones".into(), 1.into()]), (5, 4, 14));
+        assert_eq!(at(&map, &["emails".into(), 0.into()]), (7, 2, 7));
+        assert_eq!(at(&map, &["name".into(), "givenName".into()]), (9, 13, 17));
+
+        let phones = map.get(&path(&["phones".into()])).unwrap();
+        assert_eq!((phones.full.start.line, phones.full.end.line), (2, 5));
+        let emails = map.get(&path(&["emails".into()])).unwrap();
+        assert_eq!((emails.full.start.line, emails.full.end.line), (6, 7));
+    }
+
+    #[test]
+    fn test_utf16_columns() {
+        let map = SourceMap::new("prénom: 😀x\n");
+        let entry = map.get(&path(&["prénom".into()])).unwrap();
+        assert_eq!(entry.key.end.character, 6);
+        assert_eq!(
+            (entry.value.start.character, entry.value.end.character),
+            (8, 11)
+        );
+    }
+}
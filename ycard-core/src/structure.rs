@@ -0,0 +1,268 @@
+//! Structural validation of raw yCard JSON against the field definitions
+//! generated from schema.json. Used by `ValidationMode::SchemaOnly`.
+
+use crate::generated_schema::{FieldDef, ENUM_VARIANTS, ROOT_FIELDS, TYPE_DEFS};
+use crate::rules::diagnostic;
+use crate::validator::{Diagnostic, DiagnosticLevel};
+use serde_json::Value;
+
+/// Check `value` against the yCard schema, reporting each violation with its JSON-pointer path
+pub fn validate_structure(value: &Value) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_object(value, ROOT_FIELDS, "", &mut diagnostics);
+    diagnostics
+}
+
+/// Escape a property name for use as a JSON-pointer reference token (RFC 6901)
+pub fn pointer_token(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Report a violation at `path`; `args` are the values named in `message`
+fn report<'a>(
+    diagnostics: &mut Vec<Diagnostic>,
+    code: &str,
+    path: &str,
+    message: String,
+    args: impl IntoIterator<Item = (&'a str, String)>,
+) {
+    report_at(
+        DiagnosticLevel::Error,
+        diagnostics,
+        code,
+        path,
+        message,
+        args,
+    );
+}
+
+/// [`report`] at `level`
+fn report_at<'a>(
+    level: DiagnosticLevel,
+    diagnostics: &mut Vec<Diagnostic>,
+    code: &str,
+    path: &str,
+    message: String,
+    args: impl IntoIterator<Item = (&'a str, String)>,
+) {
+    let pointer = if path.is_empty() { "/" } else { path };
+    let args = std::iter::once(("path", pointer.to_string())).chain(args);
+    let mut diag = diagnostic(level, code, format!("{}: {}", pointer, message)).with_args(args);
+    diag.path = Some(path.to_string());
+    diagnostics.push(diag);
+}
+
+/// Arguments of a `schema-type` message
+fn type_args(expected: &str, value: &Value) -> [(&'static str, String); 2] {
+    [
+        ("expected", expected.to_string()),
+        ("found", type_name(value).to_string()),
+    ]
+}
+
+fn check_object(value: &Value, fields: &[FieldDef], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(object) = value.as_object() else {
+        report(
+            diagnostics,
+            "schema-type",
+            path,
+            format!("expected object, found {}", type_name(value)),
+            type_args("object", value),
+        );
+        return;
+    };
+
+    for field in fields {
+        let field_path = format!("{}/{}", path, pointer_token(field.name));
+        match object.get(field.name) {
+            None | Some(Value::Null) if field.required => report(
+                diagnostics,
+                "schema-required",
+                &field_path,
+                format!("required property \"{}\" is missing", field.name),
+                [("property", field.name.to_string())],
+            ),
+            // Optional fields serialize as null when unset
+            None | Some(Value::Null) => {}
+            Some(value) => check_value(value, field.ty, &field_path, diagnostics),
+        }
+    }
+
+    for key in object.keys() {
+        if !fields.iter().any(|f| f.name == key) {
+            report(
+                diagnostics,
+                "schema-unknown-property",
+                &format!("{}/{}", path, pointer_token(key)),
+                format!("unknown property \"{}\"", key),
+                [("property", key.to_string())],
+            );
+        }
+    }
+}
+
+fn check_value(value: &Value, ty: &str, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if let Some(item_ty) = ty.strip_suffix("[]") {
+        match value.as_array() {
+            Some(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    check_value(item, item_ty, &format!("{}/{}", path, i), diagnostics);
+                }
+            }
+            None => report(
+                diagnostics,
+                "schema-type",
+                path,
+                format!("expected array of {}, found {}", item_ty, type_name(value)),
+                type_args(ty, value),
+            ),
+        }
+        return;
+    }
+
+    let matches = match ty {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        _ => {
+            if let Some((_, variants)) = ENUM_VARIANTS.iter().find(|(name, _)| *name == ty) {
+                check_enum(value, ty, variants, path, diagnostics);
+                return;
+            }
+            if let Some(def) = TYPE_DEFS.iter().find(|def| def.name == ty) {
+                check_object(value, def.fields, path, diagnostics);
+                return;
+            }
+            true
+        }
+    };
+
+    if !matches {
+        report(
+            diagnostics,
+            "schema-type",
+            path,
+            format!("expected {}, found {}", ty, type_name(value)),
+            type_args(ty, value),
+        );
+    }
+}
+
+fn check_enum(
+    value: &Value,
+    ty: &str,
+    variants: &[&str],
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match value.as_str() {
+        Some(s) if variants.contains(&s) => {}
+        // Other strings deserialize as the type's custom variant
+        Some(s) => report_at(
+            DiagnosticLevel::Hint,
+            diagnostics,
+            "schema-enum",
+            path,
+            format!(
+                "\"{}\" is a custom {} (known values: {})",
+                s,
+                ty,
+                variants.join(", ")
+            ),
+            [
+                ("value", s.to_string()),
+                ("type", ty.to_string()),
+                ("expected", variants.join(", ")),
+            ],
+        ),
+        None => report(
+            diagnostics,
+            "schema-type",
+            path,
+            format!("expected {}, found {}", ty, type_name(value)),
+            type_args(ty, value),
+        ),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_structural_violations() {
+        let card = json!({
+            "uid": "abc",
+            "phones": [
+                {"number": "+15551234567", "type": ["mobile"]},
+                {"number": 5551234567u64, "type": "work"},
+                {"type": ["cellular"]}
+            ],
+            "emails": {"address": "jane@example.com"},
+            "name": {"givenName": "Jane", "nick/name": "JJ"},
+            "favouriteColour": "blue"
+        });
+
+        let found: Vec<(String, String)> = validate_structure(&card)
+            .into_iter()
+            .map(|d| (d.code.unwrap(), d.path.unwrap()))
+            .collect();
+        let expected = [
+            ("schema-required", "/version"),
+            ("schema-type", "/name/givenName"),
+            ("schema-unknown-property", "/name/nick~1name"),
+            ("schema-type", "/phones/1/number"),
+            ("schema-type", "/phones/1/type"),
+            ("schema-required", "/phones/2/number"),
+            ("schema-enum", "/phones/2/type/0"),
+            ("schema-type", "/emails"),
+            ("schema-unknown-property", "/favouriteColour"),
+        ];
+        assert_eq!(found.len(), expected.len(), "{:?}", found);
+        for (code, path) in expected {
+            assert!(
+                found.iter().any(|(c, p)| c == code && p == path),
+                "missing {} at {}",
+                code,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_enum_values() {
+        let card = json!({"version": 1, "phones": [{"number": "+15551234567", "type": ["pager", "cellular"]}]});
+        let diagnostics = validate_structure(&card);
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("schema-enum"));
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Hint);
+        assert_eq!(diagnostics[0].path.as_deref(), Some("/phones/0/type/1"));
+    }
+
+    #[test]
+    fn test_message_args() {
+        let diagnostics = validate_structure(&json!({"version": 1, "phones": [{"number": 1}]}));
+        let args = &diagnostics[0].message_args.as_ref().unwrap().args;
+        assert_eq!(args["path"], "/phones/0/number");
+        assert_eq!(args["expected"], "string");
+        assert_eq!(args["found"], "number");
+    }
+
+    #[test]
+    fn test_canonical_card_is_valid() {
+        let card = serde_json::to_value(crate::schema::YCard::default()).unwrap();
+        assert!(validate_structure(&card).is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+//! Text folding and word segmentation.
+//!
+//! With the `icu` feature, keys are folded with full Unicode case folding and
+//! NFKC, so fullwidth `ＥＭＡＩＬ` matches `email`, `ΟΔΟΣ` matches `οδός` and
+//! `STRASSE` matches `straße`. Words are split with ICU's dictionary and LSTM
+//! segmenters, which find word boundaries in Thai, Japanese and Chinese text
+//! written without spaces.
+//!
+//! Without it, keys are lowercased and words are split at spaces,
+//! punctuation and changes of script, which separates kanji from kana but
+//! leaves a run of one script whole.
+
+use unicode_script::{Script, UnicodeScript};
+
+/// Fold case, compatibility forms and Latin diacritics for matching keys
+pub fn fold_key(key: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    // Decompose, drop combining diacritical marks, then recompose what is
+    // left (Hangul syllables, kana with voicing marks)
+    let stripped = |s: &str| -> String {
+        s.nfd()
+            .filter(|c| !('\u{0300}'..='\u{036F}').contains(c))
+            .nfc()
+            .collect()
+    };
+
+    #[cfg(feature = "icu")]
+    let folded = {
+        use icu_casemap::CaseMapper;
+        use icu_normalizer::ComposingNormalizer;
+
+        // NFKC_Casefold: compatibility forms first, then the fold, then
+        // NFKC again for characters the fold decomposes
+        let nfkc = ComposingNormalizer::new_nfkc();
+        let folded = CaseMapper::new().fold_string(&nfkc.normalize(key));
+        stripped(&nfkc.normalize(&folded))
+    };
+
+    #[cfg(not(feature = "icu"))]
+    let folded = stripped(key).to_lowercase();
+
+    // Turkish and Azerbaijani keys are often typed without the dotless ı
+    // (`ADI` for `adı`); İ already folds to i once its dot is dropped
+    folded.replace('ı', "i")
+}
+
+/// The words of `text`, without spaces and punctuation
+pub fn segment_words(text: &str) -> Vec<&str> {
+    #[cfg(feature = "icu")]
+    {
+        use icu_segmenter::WordSegmenter;
+
+        thread_local! {
+            // Building the segmenter loads its dictionaries and models
+            static SEGMENTER: WordSegmenter = WordSegmenter::new_auto();
+        }
+
+        SEGMENTER.with(|segmenter| {
+            // Dictionary segments are not always typed as words, so keep
+            // every segment with a letter or digit in it
+            let mut words = Vec::new();
+            let mut start = 0;
+            for end in segmenter.segment_str(text) {
+                let segment = &text[start..end];
+                if segment.chars().any(char::is_alphanumeric) {
+                    words.push(segment);
+                }
+                start = end;
+            }
+            words
+        })
+    }
+
+    #[cfg(not(feature = "icu"))]
+    {
+        let mut words = Vec::new();
+        let mut start = None;
+        let mut previous = Script::Common;
+        for (i, c) in text.char_indices() {
+            let script = c.script();
+            let separator = !c.is_alphanumeric();
+            if let Some(s) = start {
+                let changed = script != previous
+                    && !matches!(script, Script::Common | Script::Inherited)
+                    && !matches!(previous, Script::Common | Script::Inherited);
+                if separator || changed {
+                    words.push(&text[s..i]);
+                    start = None;
+                }
+            }
+            if !separator {
+                if start.is_none() {
+                    start = Some(i);
+                }
+                previous = script;
+            }
+        }
+        if let Some(s) = start {
+            words.push(&text[s..]);
+        }
+        words
+    }
+}
+
+/// Split a Chinese, Japanese or Thai personal name into `(family, given)`.
+/// Han and kana names put the family name first and are split at the first
+/// word boundary; Thai names put the given name first and are split at the
+/// first space. Other names are left whole.
+pub fn split_name(name: &str) -> Option<(String, String)> {
+    let name = name.trim();
+    let has = |scripts: &[Script]| name.chars().any(|c| scripts.contains(&c.script()));
+
+    if has(&[Script::Thai]) {
+        let (given, family) = name.split_once(char::is_whitespace)?;
+        return Some((family.trim().to_string(), given.to_string()));
+    }
+    if has(&[Script::Han, Script::Hiragana, Script::Katakana]) {
+        let words = segment_words(name);
+        let (family, given) = words.split_first()?;
+        if given.is_empty() || name.chars().any(|c| !c.is_alphabetic() && c != ' ') {
+            return None;
+        }
+        return Some((family.to_string(), given.concat()));
+    }
+    None
+}
+
+/// Whether `text` is written in a script that does not separate words
+/// with spaces
+pub fn is_unspaced_script(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(
+            c.script(),
+            Script::Han
+                | Script::Hiragana
+                | Script::Katakana
+                | Script::Thai
+                | Script::Lao
+                | Script::Khmer
+                | Script::Myanmar
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_key() {
+        assert_eq!(fold_key("Téléphone"), "telephone");
+        assert_eq!(fold_key("전화"), "전화");
+        // İ lowercases to i plus a combining dot, which is stripped
+        assert_eq!(fold_key("YÖNETİCİ"), "yonetici");
+        assert_eq!(fold_key("ADI"), fold_key("adı"));
+        assert_eq!(fold_key("soyadı"), "soyadi");
+    }
+
+    #[cfg(feature = "icu")]
+    #[test]
+    fn test_fold_key_icu() {
+        assert_eq!(fold_key("ＥＭＡＩＬ"), "email");
+        assert_eq!(fold_key("ΟΔΟΣ"), fold_key("οδός"));
+        assert_eq!(fold_key("STRASSE"), fold_key("straße"));
+    }
+
+    #[test]
+    fn test_segment_words() {
+        assert_eq!(
+            segment_words("Jane  Doe-Smith"),
+            vec!["Jane", "Doe", "Smith"]
+        );
+        // Kanji and kana split apart either way
+        assert_eq!(segment_words("山田はなこ")[0], "山田");
+    }
+
+    #[test]
+    fn test_split_name() {
+        let split = |s| split_name(s).unwrap();
+        assert_eq!(split("山田はなこ"), ("山田".into(), "はなこ".into()));
+        assert_eq!(split("สมชาย ใจดี"), ("ใจดี".into(), "สมชาย".into()));
+        assert_eq!(split_name("Jane Doe"), None);
+        assert_eq!(split_name("株式会社（東京）"), None);
+    }
+
+    #[cfg(feature = "icu")]
+    #[test]
+    fn test_segment_words_icu() {
+        assert_eq!(segment_words("สมชาย ใจดี")[0], "สมชาย");
+        assert_eq!(
+            segment_words("北京市朝阳区建国路88号"),
+            vec!["北京市", "朝阳", "区", "建国", "路", "88", "号"]
+        );
+        // The dictionary knows where a kanji family name ends
+        assert_eq!(
+            split_name("佐藤花子").unwrap(),
+            ("佐藤".into(), "花子".into())
+        );
+    }
+}
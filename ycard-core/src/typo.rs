@@ -0,0 +1,122 @@
+//! "Did you mean" suggestions for unknown keys.
+//!
+//! Keys are compared after the same folding alias lookups use, so `Emial`
+//! and `emial` are the same typo, and distances count a swapped pair of
+//! letters as one edit.
+
+use crate::i18n::normalize_key;
+use serde::{Deserialize, Serialize};
+
+/// Edits beyond which two keys are considered unrelated
+const MAX_DISTANCE: usize = 2;
+
+/// Confidence below which no suggestion is made
+const MIN_CONFIDENCE: f64 = 0.5;
+
+/// The known key nearest to a misspelled one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeySuggestion {
+    /// The key or alias as written in its table, e.g. `email` or `téléphone`
+    pub key: String,
+    /// The canonical field it stands for
+    pub target: String,
+    /// Edits between the folded keys
+    pub distance: usize,
+    /// 0.0 to 1.0; halved when another target is just as close
+    pub confidence: f64,
+}
+
+/// The candidate nearest to `key`, as `(spelling, target)` pairs. Returns
+/// `None` when nothing is within two edits or half the key's length.
+pub fn suggest_key<'a>(
+    key: &str,
+    candidates: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<KeySuggestion> {
+    let folded = normalize_key(key);
+    let mut best: Option<KeySuggestion> = None;
+    let mut ambiguous = false;
+
+    for (spelling, target) in candidates {
+        let candidate = normalize_key(spelling);
+        let distance = strsim::damerau_levenshtein(&folded, &candidate);
+        if distance > MAX_DISTANCE {
+            continue;
+        }
+        let longest = folded.chars().count().max(candidate.chars().count()).max(1);
+        let confidence = 1.0 - distance as f64 / longest as f64;
+        if confidence < MIN_CONFIDENCE {
+            continue;
+        }
+
+        match &best {
+            Some(current) if distance > current.distance => continue,
+            Some(current) if distance == current.distance => {
+                if current.target != target {
+                    ambiguous = true;
+                }
+                // Among equals, keep the most similar, then the canonical name
+                if confidence < current.confidence
+                    || (confidence == current.confidence && current.key == current.target)
+                {
+                    continue;
+                }
+            }
+            _ => ambiguous = false,
+        }
+        best = Some(KeySuggestion {
+            key: spelling.to_string(),
+            target: target.to_string(),
+            distance,
+            confidence,
+        });
+    }
+
+    best.map(|mut suggestion| {
+        if ambiguous {
+            suggestion.confidence /= 2.0;
+        }
+        suggestion.confidence = (suggestion.confidence * 100.0).round() / 100.0;
+        suggestion
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANDIDATES: [(&str, &str); 6] = [
+        ("emails", "emails"),
+        ("email", "emails"),
+        ("phones", "phones"),
+        ("téléphone", "phones"),
+        ("name", "name"),
+        ("uid", "uid"),
+    ];
+
+    #[test]
+    fn test_suggest_key() {
+        let suggestion = suggest_key("emial", CANDIDATES).unwrap();
+        assert_eq!(suggestion.key, "email");
+        assert_eq!(suggestion.distance, 1);
+        assert_eq!(suggestion.confidence, 0.8);
+
+        // Folding ignores case and diacritics on both sides
+        let suggestion = suggest_key("Telephon", CANDIDATES).unwrap();
+        assert_eq!(suggestion.key, "téléphone");
+        assert_eq!(suggestion.target, "phones");
+
+        assert_eq!(suggest_key("nmae", CANDIDATES).unwrap().distance, 1);
+        assert_eq!(suggest_key("nickname", CANDIDATES), None);
+        // Two edits on a three-letter key is too far
+        assert_eq!(suggest_key("abd", CANDIDATES), None);
+    }
+
+    #[test]
+    fn test_ambiguous_suggestion() {
+        // `phone` is one edit from `phones` and from `phono`, which maps elsewhere
+        let candidates = [("phones", "phones"), ("phono", "urls")];
+        let suggestion = suggest_key("phone", candidates).unwrap();
+        assert_eq!(suggestion.target, "phones");
+        assert_eq!(suggestion.confidence, 0.42);
+    }
+}
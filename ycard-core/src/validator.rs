@@ -1,5 +1,10 @@
+use crate::parser::yaml_string;
+use crate::rules::{builtin_rules, Rule};
 use crate::schema::YCard;
+use crate::source_map::{self, SourceMap};
+use crate::structure::validate_structure;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -19,9 +24,58 @@ pub struct Diagnostic {
     pub code: Option<String>,
     pub range: Option<Range>,
     pub fixes: Vec<CodeFix>,
+    /// JSON pointer to the offending value, for structural and rule diagnostics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// How to render `message` from a message catalog; absent when the
+    /// message is only available in English
+    #[serde(
+        default,
+        rename = "messageArgs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub message_args: Option<MessageArgs>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Catalog key and parameters of a diagnostic message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageArgs {
+    /// The diagnostic code, or `code.variant` for an alternate wording
+    pub id: String,
+    pub args: BTreeMap<String, String>,
+}
+
+impl Diagnostic {
+    /// Make the message localizable under the diagnostic's own code
+    pub fn with_args<'a>(self, args: impl IntoIterator<Item = (&'a str, String)>) -> Self {
+        let id = self.code.clone().unwrap_or_default();
+        self.with_message_id(&id, args)
+    }
+
+    /// Make the message localizable under catalog key `id`
+    pub fn with_message_id<'a>(
+        mut self,
+        id: &str,
+        args: impl IntoIterator<Item = (&'a str, String)>,
+    ) -> Self {
+        self.message_args = Some(MessageArgs::new(id, args));
+        self
+    }
+}
+
+impl MessageArgs {
+    pub fn new<'a>(id: &str, args: impl IntoIterator<Item = (&'a str, String)>) -> Self {
+        Self {
+            id: id.to_string(),
+            args: args
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DiagnosticLevel {
     Error,
     Warning,
@@ -29,6 +83,18 @@ pub enum DiagnosticLevel {
     Hint,
 }
 
+impl DiagnosticLevel {
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "error" | "err" => Some(DiagnosticLevel::Error),
+            "warning" | "warn" => Some(DiagnosticLevel::Warning),
+            "info" | "information" => Some(DiagnosticLevel::Info),
+            "hint" | "suggestion" => Some(DiagnosticLevel::Hint),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Range {
     pub start: Position,
@@ -45,7 +111,16 @@ pub struct Position {
 pub struct CodeFix {
     pub title: String,
     pub kind: String,
-    pub edit: TextEdit,
+    /// The edit to the source; absent for a fix on the canonical model
+    /// until [`locate`] finds its value in the source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit: Option<TextEdit>,
+    /// JSON pointer to the value a fix on the canonical model replaces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// The replacement value at `path`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +129,39 @@ pub struct TextEdit {
     pub new_text: String,
 }
 
+/// Give diagnostics and fixes made on the canonical model ranges and edits
+/// in `source`, the YAML `ycard` was parsed from. A value is only located
+/// where the source holds it as written, so a fix never lands on a value
+/// that lenient parsing moved, merged or rewrote.
+pub fn locate(diagnostics: &mut [Diagnostic], ycard: &YCard, source: &str) {
+    let Ok(model) = serde_json::to_value(ycard) else {
+        return;
+    };
+    let map = SourceMap::new(source);
+    let find = |pointer: &str| -> Option<Range> {
+        let current = model.pointer(pointer)?.as_str()?;
+        let range = map.get(&source_map::pointer_path(pointer)?)?.value.clone();
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(source_map::slice(source, &range)?).ok()?;
+        (written.as_str() == Some(current)).then_some(range)
+    };
+
+    for diagnostic in diagnostics {
+        if diagnostic.range.is_none() {
+            diagnostic.range = diagnostic.path.as_deref().and_then(find);
+        }
+        for fix in diagnostic.fixes.iter_mut().filter(|f| f.edit.is_none()) {
+            let (Some(path), Some(value)) = (&fix.path, &fix.value) else {
+                continue;
+            };
+            fix.edit = find(path).map(|range| TextEdit {
+                range,
+                new_text: yaml_string(value),
+            });
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ValidationMode {
     Lenient,
@@ -61,148 +169,161 @@ pub enum ValidationMode {
     SchemaOnly,
 }
 
+/// Rule code under which structural (SchemaOnly) diagnostics are reported
+pub const STRUCTURE_RULE: &str = "schema";
+
 pub struct Validator {
     mode: ValidationMode,
+    rules: Vec<Box<dyn Rule>>,
+    enabled: HashMap<String, bool>,
+    severities: HashMap<String, DiagnosticLevel>,
 }
 
 impl Validator {
     pub fn new(mode: ValidationMode) -> Self {
-        Self { mode }
+        Self {
+            mode,
+            rules: builtin_rules(),
+            enabled: HashMap::new(),
+            severities: HashMap::new(),
+        }
+    }
+
+    /// Register a custom rule. A rule with the same code replaces the existing one.
+    pub fn register_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rules.retain(|r| r.code() != rule.code());
+        self.rules.push(rule);
+    }
+
+    pub fn with_rule(mut self, rule: Box<dyn Rule>) -> Self {
+        self.register_rule(rule);
+        self
+    }
+
+    /// Codes of all registered rules
+    pub fn rule_codes(&self) -> Vec<&str> {
+        self.rules.iter().map(|r| r.code()).collect()
+    }
+
+    /// Enable a rule by its code, or re-enable a single diagnostic code
+    pub fn enable_rule(&mut self, code: &str) {
+        self.enabled.insert(code.to_string(), true);
+    }
+
+    /// Disable a rule by its code, or suppress a single diagnostic code
+    pub fn disable_rule(&mut self, code: &str) {
+        self.enabled.insert(code.to_string(), false);
+    }
+
+    /// Override the level of diagnostics with this code (or every code of a rule)
+    pub fn set_severity(&mut self, code: &str, level: DiagnosticLevel) {
+        self.severities.insert(code.to_string(), level);
+    }
+
+    pub fn with_severity(mut self, code: &str, level: DiagnosticLevel) -> Self {
+        self.set_severity(code, level);
+        self
     }
 
     /// Validate yCard and return diagnostics
     pub fn validate(&self, ycard: &YCard) -> Result<Vec<Diagnostic>, ValidationError> {
         let mut diagnostics = Vec::new();
 
-        match self.mode {
-            ValidationMode::Lenient => {
-                self.validate_lenient(ycard, &mut diagnostics)?;
-            }
-            ValidationMode::Strict => {
-                self.validate_strict(ycard, &mut diagnostics)?;
-            }
-            ValidationMode::SchemaOnly => {
-                self.validate_schema_only(ycard, &mut diagnostics)?;
-            }
+        if matches!(self.mode, ValidationMode::SchemaOnly) {
+            let value =
+                serde_json::to_value(ycard).map_err(|e| ValidationError::SchemaViolation {
+                    message: e.to_string(),
+                })?;
+            self.check_structure(&value, &mut diagnostics);
         }
 
+        self.run_rules(ycard, &mut diagnostics)?;
         Ok(diagnostics)
     }
 
-    fn validate_lenient(
+    /// Validate raw yCard JSON. In `SchemaOnly` mode the value is checked
+    /// structurally first, so malformed input from other producers yields
+    /// diagnostics instead of a deserialization error.
+    pub fn validate_value(
         &self,
-        ycard: &YCard,
-        diagnostics: &mut Vec<Diagnostic>,
-    ) -> Result<(), ValidationError> {
-        // Check basic structure
-        if ycard.version == 0 {
-            diagnostics.push(Diagnostic {
-                level: DiagnosticLevel::Warning,
-                message: "Version should be specified (defaulting to 1)".to_string(),
-                code: Some("version-missing".to_string()),
-                range: None,
-                fixes: vec![CodeFix {
-                    title: "Add version: 1".to_string(),
-                    kind: "quickfix".to_string(),
-                    edit: TextEdit {
-                        range: Range {
-                            start: Position {
-                                line: 0,
-                                character: 0,
-                            },
-                            end: Position {
-                                line: 0,
-                                character: 0,
-                            },
-                        },
-                        new_text: "version: 1\n".to_string(),
-                    },
-                }],
-            });
-        }
+        value: &serde_json::Value,
+    ) -> Result<Vec<Diagnostic>, ValidationError> {
+        let ycard = serde_json::from_value::<YCard>(value.clone());
 
-        // Validate phones
-        if let Some(phones) = &ycard.phones {
-            for phone in phones.iter() {
-                if !phone.number.starts_with('+') {
-                    diagnostics.push(Diagnostic {
-                        level: DiagnosticLevel::Warning,
-                        message: format!(
-                            "Phone number should be in E.164 format: {}",
-                            phone.number
-                        ),
-                        code: Some("phone-format".to_string()),
-                        range: None,
-                        fixes: vec![],
-                    });
-                }
-            }
+        if !matches!(self.mode, ValidationMode::SchemaOnly) {
+            let ycard = ycard.map_err(|e| ValidationError::SchemaViolation {
+                message: e.to_string(),
+            })?;
+            return self.validate(&ycard);
         }
 
-        // Validate emails
-        if let Some(emails) = &ycard.emails {
-            for email in emails {
-                if !email.address.contains('@') {
-                    diagnostics.push(Diagnostic {
-                        level: DiagnosticLevel::Error,
-                        message: format!("Invalid email address: {}", email.address),
-                        code: Some("email-invalid".to_string()),
-                        range: None,
-                        fixes: vec![],
-                    });
-                }
-            }
+        let mut diagnostics = Vec::new();
+        self.check_structure(value, &mut diagnostics);
+        if let Ok(ycard) = ycard {
+            self.run_rules(&ycard, &mut diagnostics)?;
         }
+        Ok(diagnostics)
+    }
 
-        Ok(())
+    fn check_structure(&self, value: &serde_json::Value, diagnostics: &mut Vec<Diagnostic>) {
+        if self.enabled.get(STRUCTURE_RULE) == Some(&false) {
+            return;
+        }
+        self.collect(STRUCTURE_RULE, validate_structure(value), diagnostics);
     }
 
-    fn validate_strict(
+    fn run_rules(
         &self,
         ycard: &YCard,
         diagnostics: &mut Vec<Diagnostic>,
     ) -> Result<(), ValidationError> {
-        // All lenient validations become errors in strict mode
-        self.validate_lenient(ycard, diagnostics)?;
-
-        // Convert warnings to errors
-        for diagnostic in diagnostics.iter_mut() {
-            if matches!(diagnostic.level, DiagnosticLevel::Warning) {
-                diagnostic.level = DiagnosticLevel::Error;
+        for rule in &self.rules {
+            let enabled = self
+                .enabled
+                .get(rule.code())
+                .copied()
+                .unwrap_or_else(|| rule.enabled_by_default());
+            if !enabled || !rule.applies_to(&self.mode) {
+                continue;
             }
-        }
 
-        // Additional strict validations
-        if ycard.name.is_none() && ycard.phones.is_none() && ycard.emails.is_none() {
-            diagnostics.push(Diagnostic {
-                level: DiagnosticLevel::Error,
-                message: "At least one of name, phones, or emails must be present".to_string(),
-                code: Some("empty-contact".to_string()),
-                range: None,
-                fixes: vec![],
-            });
+            let mut emitted = Vec::new();
+            rule.check(ycard, &mut emitted)?;
+            self.collect(rule.code(), emitted, diagnostics);
         }
-
         Ok(())
     }
 
-    fn validate_schema_only(
+    /// Apply per-code disabling, strict upgrades and severity overrides
+    fn collect(
         &self,
-        ycard: &YCard,
+        rule_code: &str,
+        emitted: Vec<Diagnostic>,
         diagnostics: &mut Vec<Diagnostic>,
-    ) -> Result<(), ValidationError> {
-        // Only validate against the canonical schema structure
-        if ycard.version > 1 {
-            diagnostics.push(Diagnostic {
-                level: DiagnosticLevel::Error,
-                message: format!("Unsupported version: {}", ycard.version),
-                code: Some("version-unsupported".to_string()),
-                range: None,
-                fixes: vec![],
-            });
-        }
+    ) {
+        for mut diagnostic in emitted {
+            let code = diagnostic.code.clone().unwrap_or_default();
+            if self.enabled.get(&code) == Some(&false) {
+                continue;
+            }
 
-        Ok(())
+            // All lenient warnings become errors in strict mode
+            if matches!(self.mode, ValidationMode::Strict)
+                && matches!(diagnostic.level, DiagnosticLevel::Warning)
+            {
+                diagnostic.level = DiagnosticLevel::Error;
+            }
+
+            if let Some(level) = self
+                .severities
+                .get(&code)
+                .or_else(|| self.severities.get(rule_code))
+            {
+                diagnostic.level = level.clone();
+            }
+
+            diagnostics.push(diagnostic);
+        }
     }
 }
 
@@ -215,6 +336,7 @@ impl Default for Validator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generated_types::PhoneType;
     use crate::schema::*;
 
     #[test]
@@ -239,6 +361,7 @@ mod tests {
             addresses: None,
             manager: None,
             metadata: None,
+            ..YCard::default()
         };
 
         let diagnostics = validator.validate(&ycard).unwrap();
@@ -259,4 +382,389 @@ mod tests {
             .iter()
             .any(|d| matches!(d.level, DiagnosticLevel::Error)));
     }
+
+    #[test]
+    fn test_email_diagnostic_codes() {
+        use crate::generated_types::EmailType;
+
+        let validator = Validator::new(ValidationMode::Lenient);
+        let email = |address: &str| Email {
+            address: address.to_string(),
+            r#type: vec![EmailType::Other],
+            preferred: None,
+        };
+        let ycard = YCard {
+            emails: Some(vec![
+                email("jane@"),
+                email("jane@Example.COM"),
+                email("josé@example.com"),
+            ]),
+            ..YCard::default()
+        };
+
+        let diagnostics = validator.validate(&ycard).unwrap();
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .filter_map(|d| d.code.as_deref())
+            .collect();
+        assert_eq!(
+            codes,
+            vec!["email-domain-empty", "email-normalized", "email-smtputf8"]
+        );
+        let invalid = diagnostics[0].message_args.as_ref().unwrap();
+        assert_eq!(invalid.id, "email-domain-empty");
+        assert!(invalid.args.contains_key("address"));
+    }
+
+    #[test]
+    fn test_address_country_rules() {
+        use crate::generated_types::AddressType;
+
+        let validator = Validator::new(ValidationMode::Lenient);
+        let ycard = YCard {
+            addresses: Some(vec![Address {
+                r#type: vec![AddressType::Home],
+                formatted: None,
+                components: Some(AddressComponents {
+                    street: Some("10 Downing St".to_string()),
+                    locality: Some("London".to_string()),
+                    region: None,
+                    postal_code: Some("sw1a2aa".to_string()),
+                    country: Some("GB".to_string()),
+                }),
+            }]),
+            ..YCard::default()
+        };
+
+        let diagnostics = validator.validate(&ycard).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code.as_deref(),
+            Some("address-postal-code-format")
+        );
+        assert!(diagnostics[0].message.contains("SW1A 2AA"));
+        let args = &diagnostics[0].message_args.as_ref().unwrap().args;
+        assert_eq!(args["postalCode"], "sw1a2aa");
+        assert_eq!(args["suggestion"], "SW1A 2AA");
+    }
+
+    #[test]
+    fn test_country_region() {
+        let ycard = YCard {
+            addresses: Some(vec![Address {
+                r#type: vec![],
+                formatted: None,
+                components: Some(AddressComponents {
+                    street: None,
+                    locality: None,
+                    region: Some("US-CA".to_string()),
+                    postal_code: None,
+                    country: Some("FR".to_string()),
+                }),
+            }]),
+            ..YCard::default()
+        };
+
+        let diagnostics = Validator::new(ValidationMode::Lenient)
+            .validate(&ycard)
+            .unwrap();
+        let region = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("country-region-invalid"))
+            .unwrap();
+        assert_eq!(
+            region.message_args.as_ref().unwrap().id,
+            "country-region-invalid.country"
+        );
+    }
+
+    #[test]
+    fn test_registry_code_fixes() {
+        use crate::generated_types::AddressType;
+
+        let validator = Validator::new(ValidationMode::Lenient);
+        let ycard = YCard {
+            name: Some(Name {
+                given_name: None,
+                middle_name: None,
+                family_name: None,
+                honorific_prefix: None,
+                honorific_suffix: None,
+                display_name: Some("Jean Dupont".to_string()),
+                script: Some("latin".to_string()),
+                ..Name::default()
+            }),
+            addresses: Some(vec![Address {
+                r#type: vec![AddressType::Home],
+                formatted: Some("Paris".to_string()),
+                components: Some(AddressComponents {
+                    street: None,
+                    locality: None,
+                    region: None,
+                    postal_code: None,
+                    country: Some("France".to_string()),
+                }),
+            }]),
+            metadata: Some(Metadata {
+                locale: Some("en_US".to_string()),
+                source: None,
+            }),
+            ..YCard::default()
+        };
+
+        let diagnostics = validator.validate(&ycard).unwrap();
+        let fixes: Vec<_> = diagnostics
+            .iter()
+            .map(|d| {
+                (
+                    d.code.as_deref().unwrap(),
+                    d.fixes[0].value.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fixes,
+            vec![
+                ("country-invalid", "FR"),
+                ("locale-invalid", "en-US"),
+                ("script-invalid", "Latn"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_locate_fixes() {
+        let validate = |source: &str, code: &str| {
+            let ycard = crate::parse(source, None).unwrap();
+            let mut diagnostics = Validator::new(ValidationMode::Lenient)
+                .validate(&ycard)
+                .unwrap();
+            locate(&mut diagnostics, &ycard, source);
+            diagnostics
+                .into_iter()
+                .find(|d| d.code.as_deref() == Some(code))
+                .unwrap()
+        };
+
+        let source = "name: Jane Doe\nmetadata:\n  locale: en_us\n";
+        let d = validate(source, "locale-invalid");
+        let edit = d.fixes[0].edit.as_ref().unwrap();
+        assert_eq!(source_map::slice(source, &edit.range), Some("en_us"));
+        assert_eq!(edit.new_text, "\"en-US\"");
+        assert_eq!(d.range.as_ref().unwrap().start.line, 2);
+
+        // The tag was read from a shorthand, so there is no scalar to replace
+        let d = validate("name: Jane Doe\nlanguages: en_us\n", "locale-invalid");
+        assert!(d.fixes[0].edit.is_none());
+        assert!(d.range.is_none());
+
+        // Hidden characters are removed by replacing the whole scalar
+        let source = "name:\n  givenName: [Jane]\n  familyName: [\"D\\u200Boe\"]\n";
+        let d = validate(source, "invisible-char");
+        let edit = d.fixes[0].edit.as_ref().unwrap();
+        assert_eq!(
+            source_map::slice(source, &edit.range),
+            Some("\"D\\u200Boe\"")
+        );
+        assert_eq!(edit.new_text, "\"Doe\"");
+    }
+
+    #[test]
+    fn test_rule_configuration() {
+        let ycard = YCard {
+            emails: Some(vec![Email {
+                address: "jane@Example.com".to_string(),
+                r#type: vec![crate::generated_types::EmailType::Work],
+                preferred: None,
+            }]),
+            ..YCard::default()
+        };
+
+        let mut validator = Validator::new(ValidationMode::Lenient);
+        assert!(validator.rule_codes().contains(&"uid-missing"));
+        let codes = |v: &Validator| -> Vec<String> {
+            v.validate(&ycard)
+                .unwrap()
+                .into_iter()
+                .filter_map(|d| d.code)
+                .collect()
+        };
+        assert_eq!(codes(&validator), vec!["email-normalized"]);
+
+        validator.enable_rule("uid-missing");
+        validator.disable_rule("email-normalized");
+        validator.set_severity("uid-missing", DiagnosticLevel::Error);
+        let diagnostics = validator.validate(&ycard).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("uid-missing"));
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+
+        validator.disable_rule("uid-missing");
+        assert!(codes(&validator).is_empty());
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        struct WorkDomainRule;
+
+        impl Rule for WorkDomainRule {
+            fn code(&self) -> &str {
+                "work-email-domain"
+            }
+
+            fn check(
+                &self,
+                ycard: &YCard,
+                diagnostics: &mut Vec<Diagnostic>,
+            ) -> Result<(), ValidationError> {
+                for email in ycard.emails.iter().flatten() {
+                    let work = email
+                        .r#type
+                        .contains(&crate::generated_types::EmailType::Work);
+                    if work && !email.address.ends_with("@example.com") {
+                        diagnostics.push(crate::rules::diagnostic(
+                            DiagnosticLevel::Warning,
+                            "work-email-domain",
+                            format!("Work email must be on example.com: {}", email.address),
+                        ));
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let ycard = YCard {
+            emails: Some(vec![Email {
+                address: "jane@elsewhere.org".to_string(),
+                r#type: vec![crate::generated_types::EmailType::Work],
+                preferred: None,
+            }]),
+            ..YCard::default()
+        };
+
+        let validator = Validator::new(ValidationMode::Strict).with_rule(Box::new(WorkDomainRule));
+        let diagnostics = validator.validate(&ycard).unwrap();
+        let custom = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("work-email-domain"))
+            .unwrap();
+        assert_eq!(custom.level, DiagnosticLevel::Error);
+    }
+
+    #[test]
+    fn test_schema_only_value() {
+        let value = serde_json::json!({
+            "version": 1,
+            "emails": [{"address": "jane@example.com", "type": ["personal"]}],
+            "hobby": "chess"
+        });
+
+        let validator = Validator::new(ValidationMode::SchemaOnly);
+        let diagnostics = validator.validate_value(&value).unwrap();
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .filter_map(|d| d.code.as_deref())
+            .collect();
+        assert_eq!(
+            codes,
+            vec!["schema-enum", "schema-unknown-property"],
+            "{:?}",
+            diagnostics
+        );
+        assert_eq!(diagnostics[0].path.as_deref(), Some("/emails/0/type/0"));
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Hint);
+
+        // Deserializable input also runs the card-level SchemaOnly rules
+        let value = serde_json::json!({"version": 2});
+        let diagnostics = validator.validate_value(&value).unwrap();
+        assert_eq!(diagnostics[0].code.as_deref(), Some("version-unsupported"));
+
+        let mut validator = Validator::new(ValidationMode::SchemaOnly);
+        validator.disable_rule(STRUCTURE_RULE);
+        let value = serde_json::json!({"version": 1, "extra": true});
+        assert!(validator.validate_value(&value).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_spoofing_rules() {
+        let ycard = YCard {
+            name: Some(Name {
+                given_name: None,
+                middle_name: None,
+                family_name: None,
+                honorific_prefix: None,
+                honorific_suffix: None,
+                display_name: Some("Jane\u{200B} D\u{043E}e\u{202E}".to_string()),
+                script: None,
+                ..Name::default()
+            }),
+            emails: Some(vec![Email {
+                address: "jane@p\u{0430}ypal.com".to_string(),
+                r#type: vec![],
+                preferred: None,
+            }]),
+            ..YCard::default()
+        };
+
+        let diagnostics = Validator::new(ValidationMode::Lenient)
+            .validate(&ycard)
+            .unwrap();
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .filter_map(|d| d.code.as_deref())
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                "homograph-mixed-script",
+                "homograph-mixed-script",
+                "homograph-confusable",
+                "bidi-control",
+                "invisible-char"
+            ]
+        );
+        // Each fix removes only the characters its rule reports
+        let bidi = &diagnostics[3];
+        assert_eq!(
+            bidi.fixes[0].value.as_deref(),
+            Some("Jane\u{200B} D\u{043E}e")
+        );
+        let invisible = diagnostics.last().unwrap();
+        assert_eq!(invisible.path.as_deref(), Some("/name/displayName"));
+        assert_eq!(
+            invisible.fixes[0].value.as_deref(),
+            Some("Jane D\u{043E}e\u{202E}")
+        );
+    }
+
+    #[test]
+    fn test_preferred_multiple() {
+        let phone = |number: &str, preferred| Phone {
+            number: number.to_string(),
+            r#type: vec![PhoneType::Mobile],
+            ext: None,
+            preferred,
+            label: None,
+        };
+        let ycard = YCard {
+            phones: Some(vec![
+                phone("+15550000001", Some(true)),
+                phone("+15550000002", None),
+                phone("+15550000003", Some(true)),
+                phone("+15550000004", Some(true)),
+            ]),
+            ..YCard::default()
+        };
+
+        let diagnostics = Validator::new(ValidationMode::Lenient)
+            .validate(&ycard)
+            .unwrap();
+        let paths: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.code.as_deref() == Some("preferred-multiple"))
+            .filter_map(|d| d.path.as_deref())
+            .collect();
+        assert_eq!(paths, vec!["/phones/2/preferred", "/phones/3/preferred"]);
+        assert_eq!(ycard.preferred_phone().unwrap().number, "+15550000001");
+    }
 }
@@ -1,30 +1,47 @@
 use crate::i18n::AliasManager;
-use crate::schema::YCard;
 use serde_json;
-use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 
+#[cfg(feature = "wasm")]
+use crate::limits::ParseOptions;
+#[cfg(feature = "wasm")]
+use crate::schema::YCard;
+#[cfg(feature = "wasm")]
+use std::collections::HashMap;
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 // Global state for WASM using thread-safe alternatives
 static ALIAS_MANAGER: OnceLock<Mutex<AliasManager>> = OnceLock::new();
+#[cfg(feature = "wasm")]
 static LAST_ERROR: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+#[cfg(feature = "wasm")]
 static DOCUMENT_ARENA: OnceLock<Mutex<HashMap<i32, YCard>>> = OnceLock::new();
+#[cfg(feature = "wasm")]
 static NEXT_HANDLE: OnceLock<Mutex<i32>> = OnceLock::new();
+#[cfg(feature = "wasm")]
+static PARSE_OPTIONS: OnceLock<Mutex<ParseOptions>> = OnceLock::new();
 
 fn get_alias_manager() -> &'static Mutex<AliasManager> {
     ALIAS_MANAGER.get_or_init(|| Mutex::new(AliasManager::new()))
 }
 
+#[cfg(feature = "wasm")]
 fn get_document_arena() -> &'static Mutex<HashMap<i32, YCard>> {
     DOCUMENT_ARENA.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
+#[cfg(feature = "wasm")]
 fn get_next_handle() -> &'static Mutex<i32> {
     NEXT_HANDLE.get_or_init(|| Mutex::new(1))
 }
 
+#[cfg(feature = "wasm")]
+fn get_parse_options() -> &'static Mutex<ParseOptions> {
+    PARSE_OPTIONS.get_or_init(|| Mutex::new(ParseOptions::default()))
+}
+
+#[cfg(feature = "wasm")]
 fn set_last_error(error: &str) {
     let error_mutex = LAST_ERROR.get_or_init(|| Mutex::new(None));
     if let Ok(mut last_error) = error_mutex.lock() {
@@ -32,6 +49,7 @@ fn set_last_error(error: &str) {
     }
 }
 
+#[cfg(feature = "wasm")]
 fn get_last_error() -> Option<String> {
     let error_mutex = LAST_ERROR.get_or_init(|| Mutex::new(None));
     if let Ok(last_error) = error_mutex.lock() {
@@ -45,6 +63,23 @@ fn get_last_error() -> Option<String> {
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn yc_parse(ptr: i32, len: i32) -> i32 {
+    let options = if let Ok(options) = get_parse_options().lock() {
+        options.clone()
+    } else {
+        set_last_error("Failed to acquire parse options lock");
+        return -1;
+    };
+
+    // Reject oversized uploads before touching the buffer
+    if len < 0 {
+        set_last_error("Invalid input length");
+        return -1;
+    }
+    if let Err(e) = options.check_input_len(len as usize) {
+        set_last_error(&format!("Parse error: {}", e));
+        return -1;
+    }
+
     let slice = unsafe { std::slice::from_raw_parts(ptr as *const u8, len as usize) };
     let input = match std::str::from_utf8(slice) {
         Ok(s) => s,
@@ -61,7 +96,7 @@ pub fn yc_parse(ptr: i32, len: i32) -> i32 {
         return -1;
     };
 
-    let parser = crate::parser::Parser::with_alias_manager(alias_manager);
+    let parser = crate::parser::Parser::with_alias_manager(alias_manager).with_options(options);
     match parser.parse_lenient(input, None) {
         Ok(ycard) => {
             let handle = if let Ok(mut next_handle) = get_next_handle().lock() {
@@ -150,6 +185,7 @@ pub fn yc_validate(handle: i32, mode: u32) -> i32 {
     let validation_mode = match mode {
         0 => crate::validator::ValidationMode::Lenient,
         1 => crate::validator::ValidationMode::Strict,
+        2 => crate::validator::ValidationMode::SchemaOnly,
         _ => crate::validator::ValidationMode::Lenient,
     };
 
@@ -210,6 +246,33 @@ pub fn yc_load_alias_pack(ptr: i32, len: i32) -> i32 {
     }
 }
 
+/// Configure parse limits from JSON, e.g. `{"maxInputBytes": 65536, "maxDepth": 16}`.
+/// Omitted fields keep their defaults.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn yc_set_parse_options(ptr: i32, len: i32) -> i32 {
+    if len < 0 {
+        set_last_error("Invalid input length");
+        return -1;
+    }
+    let slice = unsafe { std::slice::from_raw_parts(ptr as *const u8, len as usize) };
+    let options: ParseOptions = match serde_json::from_slice(slice) {
+        Ok(options) => options,
+        Err(e) => {
+            set_last_error(&format!("Invalid parse options: {}", e));
+            return -1;
+        }
+    };
+
+    if let Ok(mut current) = get_parse_options().lock() {
+        *current = options;
+        0
+    } else {
+        set_last_error("Failed to acquire parse options lock");
+        -1
+    }
+}
+
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn yc_clear_aliases() -> i32 {
@@ -222,6 +285,31 @@ pub fn yc_clear_aliases() -> i32 {
     }
 }
 
+/// Unload the alias pack named by the UTF-8 at `ptr`: 0 when removed,
+/// 1 when no such pack is loaded
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn yc_remove_alias_pack(ptr: i32, len: i32) -> i32 {
+    let slice = unsafe { std::slice::from_raw_parts(ptr as *const u8, len as usize) };
+    let name = match std::str::from_utf8(slice) {
+        Ok(s) => s,
+        Err(e) => {
+            set_last_error(&format!("Invalid UTF-8: {}", e));
+            return -1;
+        }
+    };
+
+    if let Ok(mut manager) = get_alias_manager().lock() {
+        match manager.remove_pack(name) {
+            Some(_) => 0,
+            None => 1,
+        }
+    } else {
+        set_last_error("Failed to acquire alias manager lock");
+        -1
+    }
+}
+
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn yc_set_default_locale(ptr: i32, len: i32) -> i32 {
@@ -268,7 +356,11 @@ pub fn yc_last_error() -> i32 {
 pub fn parse_ycard_lenient(input: &str, locale: Option<String>) -> Result<JsValue, JsValue> {
     use crate::parser::Parser;
 
-    let parser = Parser::new();
+    let options = get_parse_options()
+        .lock()
+        .map(|o| o.clone())
+        .unwrap_or_default();
+    let parser = Parser::new().with_options(options);
     let locale_ref = locale.as_deref();
     match parser.parse_lenient(input, locale_ref) {
         Ok(ycard) => Ok(serde_wasm_bindgen::to_value(&ycard)?),
@@ -281,13 +373,60 @@ pub fn parse_ycard_lenient(input: &str, locale: Option<String>) -> Result<JsValu
 pub fn parse_ycard_strict(input: &str) -> Result<JsValue, JsValue> {
     use crate::parser::Parser;
 
-    let parser = Parser::new();
+    let options = get_parse_options()
+        .lock()
+        .map(|o| o.clone())
+        .unwrap_or_default();
+    let parser = Parser::new().with_options(options);
     match parser.parse_strict(input) {
         Ok(ycard) => Ok(serde_wasm_bindgen::to_value(&ycard)?),
         Err(e) => Err(JsValue::from_str(&format!("Parse error: {}", e))),
     }
 }
 
+/// Lenient parse that never fails: returns `{ ycard, diagnostics }` with
+/// unreadable fields reported as error diagnostics
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn parse_ycard_partial(input: &str, locale: Option<String>) -> Result<JsValue, JsValue> {
+    use crate::parser::Parser;
+
+    let options = get_parse_options()
+        .lock()
+        .map(|o| o.clone())
+        .unwrap_or_default();
+    let parser = Parser::new().with_options(options);
+    let (ycard, diagnostics) = parser.parse_partial(input, locale.as_deref());
+
+    #[derive(serde::Serialize)]
+    struct PartialParse {
+        ycard: crate::schema::YCard,
+        diagnostics: Vec<crate::validator::Diagnostic>,
+    }
+    Ok(serde_wasm_bindgen::to_value(&PartialParse {
+        ycard,
+        diagnostics,
+    })?)
+}
+
+/// The locale lenient parsing would use for `input` when given none:
+/// `{ locale, confidence, source }`, or `null` without any evidence
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn detect_locale(input: &str) -> Result<JsValue, JsValue> {
+    use crate::parser::Parser;
+
+    let options = get_parse_options()
+        .lock()
+        .map(|o| o.clone())
+        .unwrap_or_default();
+    let parser = Parser::new().with_options(options);
+    match parser.detect_locale(input) {
+        Ok(guess) => Ok(serde_wasm_bindgen::to_value(&guess)?),
+        Err(e) => Err(JsValue::from_str(&format!("Parse error: {}", e))),
+    }
+}
+
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn set_default_locale(locale: &str) -> Result<(), JsValue> {
@@ -315,23 +454,100 @@ pub fn load_alias_pack(content: &str) -> Result<(), JsValue> {
     }
 }
 
+/// Load a pack under `name` at `priority`, replacing any pack of that name
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn load_named_alias_pack(name: &str, priority: i32, content: &str) -> Result<(), JsValue> {
+    if let Ok(mut manager) = get_alias_manager().lock() {
+        manager
+            .load_named_pack(name, priority, content)
+            .map_err(|e| JsValue::from_str(&format!("Failed to load alias pack: {}", e)))
+    } else {
+        Err(JsValue::from_str("Failed to acquire alias manager lock"))
+    }
+}
+
+/// Unload the pack called `name`; `false` when no such pack is loaded
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn remove_alias_pack(name: &str) -> Result<bool, JsValue> {
+    if let Ok(mut manager) = get_alias_manager().lock() {
+        Ok(manager.remove_pack(name).is_some())
+    } else {
+        Err(JsValue::from_str("Failed to acquire alias manager lock"))
+    }
+}
+
+/// Loaded packs as `{name, version, priority, schemaVersion, locales}`,
+/// lowest priority first
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn list_alias_packs() -> Result<JsValue, JsValue> {
+    if let Ok(manager) = get_alias_manager().lock() {
+        Ok(serde_wasm_bindgen::to_value(&manager.packs())?)
+    } else {
+        Err(JsValue::from_str("Failed to acquire alias manager lock"))
+    }
+}
+
+/// Every locale the loaded packs cover
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn list_alias_locales() -> Result<JsValue, JsValue> {
+    if let Ok(manager) = get_alias_manager().lock() {
+        Ok(serde_wasm_bindgen::to_value(&manager.locales())?)
+    } else {
+        Err(JsValue::from_str("Failed to acquire alias manager lock"))
+    }
+}
+
+/// Which pack and locale resolve `word`; `kind` is `key`, `type` or
+/// `country`. Returns `null` when nothing matches.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn explain_alias(word: &str, kind: &str, locale: Option<String>) -> Result<JsValue, JsValue> {
+    let kind = crate::i18n::AliasKind::from_str_opt(kind)
+        .ok_or_else(|| JsValue::from_str("Invalid alias kind"))?;
+    if let Ok(manager) = get_alias_manager().lock() {
+        Ok(serde_wasm_bindgen::to_value(&manager.explain(
+            kind,
+            word,
+            locale.as_deref(),
+        ))?)
+    } else {
+        Err(JsValue::from_str("Failed to acquire alias manager lock"))
+    }
+}
+
+/// Validate canonical yCard JSON; messages are in `locale` when a loaded
+/// pack has a catalog for it, else in the default locale or English
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
-pub fn validate_ycard(ycard_json: &str, mode: &str) -> Result<JsValue, JsValue> {
+pub fn validate_ycard(
+    ycard_json: &str,
+    mode: &str,
+    locale: Option<String>,
+) -> Result<JsValue, JsValue> {
     use crate::validator::{ValidationMode, Validator};
 
     let validation_mode = match mode {
         "strict" => ValidationMode::Strict,
         "lenient" => ValidationMode::Lenient,
+        "schema-only" => ValidationMode::SchemaOnly,
         _ => return Err(JsValue::from_str("Invalid validation mode")),
     };
 
-    let ycard: YCard = serde_json::from_str(ycard_json)
+    let value: serde_json::Value = serde_json::from_str(ycard_json)
         .map_err(|e| JsValue::from_str(&format!("Invalid yCard JSON: {}", e)))?;
 
     let validator = Validator::new(validation_mode);
-    match validator.validate(&ycard) {
-        Ok(diagnostics) => Ok(serde_wasm_bindgen::to_value(&diagnostics)?),
+    match validator.validate_value(&value) {
+        Ok(mut diagnostics) => {
+            if let Ok(manager) = get_alias_manager().lock() {
+                crate::messages::localize(&mut diagnostics, &manager, locale.as_deref());
+            }
+            Ok(serde_wasm_bindgen::to_value(&diagnostics)?)
+        }
         Err(e) => Err(JsValue::from_str(&format!("Validation error: {}", e))),
     }
 }
@@ -366,7 +582,11 @@ pub fn parse(input: &str, locale: Option<String>) -> Result<JsValue, JsValue> {
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
-pub fn validate(ycard_value: JsValue, _mode: JsValue) -> Result<JsValue, JsValue> {
+pub fn validate(
+    ycard_value: JsValue,
+    _mode: JsValue,
+    locale: Option<String>,
+) -> Result<JsValue, JsValue> {
     // Convert JsValue back to JSON string for validation
     let ycard_json = js_sys::JSON::stringify(&ycard_value)
         .map_err(|_| JsValue::from_str("Failed to stringify yCard"))?;
@@ -375,7 +595,7 @@ pub fn validate(ycard_value: JsValue, _mode: JsValue) -> Result<JsValue, JsValue
         .ok_or_else(|| JsValue::from_str("Invalid JSON string"))?;
 
     // Use lenient validation by default
-    validate_ycard(&ycard_json_str, "lenient")
+    validate_ycard(&ycard_json_str, "lenient", locale)
 }
 
 #[cfg(feature = "wasm")]
@@ -419,7 +639,7 @@ pub fn loadAliasPack(content: &str) -> Result<(), JsValue> {
 #[cfg(not(feature = "wasm"))]
 pub mod c_api {
     use super::*;
-    use std::ffi::{CStr, CString};
+    use std::ffi::CString;
     use std::os::raw::{c_char, c_int};
 
     #[repr(C)]
@@ -528,4 +748,213 @@ pub mod c_api {
             -1
         }
     }
+
+    fn write_error(out_err: *mut yc_error, message: &str) {
+        if !out_err.is_null() {
+            unsafe {
+                (*out_err).code = -1;
+                let msg = CString::new(message.replace('\0', "")).unwrap();
+                (*out_err).message = msg.into_raw();
+            }
+        }
+    }
+
+    fn write_json<T: serde::Serialize>(
+        value: &T,
+        out_buf: *mut yc_buffer,
+        out_err: *mut yc_error,
+    ) -> c_int {
+        if out_buf.is_null() {
+            write_error(out_err, "Output buffer is null");
+            return -1;
+        }
+        match serde_json::to_vec(value) {
+            Ok(bytes) => {
+                let mut bytes = std::mem::ManuallyDrop::new(bytes);
+                unsafe {
+                    (*out_buf).data = bytes.as_mut_ptr();
+                    (*out_buf).len = bytes.len();
+                    (*out_buf).capacity = bytes.capacity();
+                }
+                0
+            }
+            Err(e) => {
+                write_error(out_err, &format!("JSON serialization error: {}", e));
+                -1
+            }
+        }
+    }
+
+    fn read_str<'a>(ptr: *const c_char, len: usize) -> Result<&'a str, String> {
+        if ptr.is_null() {
+            return Err("Null string".to_string());
+        }
+        let slice = unsafe { std::slice::from_raw_parts(ptr as *const u8, len) };
+        std::str::from_utf8(slice).map_err(|e| format!("Invalid UTF-8: {}", e))
+    }
+
+    /// Load a pack under `name` at `priority`, replacing any pack of that name
+    #[no_mangle]
+    pub extern "C" fn yc_load_named_alias_pack(
+        name_utf8: *const c_char,
+        name_len: usize,
+        bytes: *const u8,
+        len: usize,
+        priority: c_int,
+        out_err: *mut yc_error,
+    ) -> c_int {
+        let name = match read_str(name_utf8, name_len) {
+            Ok(name) => name,
+            Err(e) => {
+                write_error(out_err, &e);
+                return -1;
+            }
+        };
+        let json = match read_str(bytes as *const c_char, len) {
+            Ok(json) => json,
+            Err(e) => {
+                write_error(out_err, &e);
+                return -1;
+            }
+        };
+
+        if let Ok(mut manager) = get_alias_manager().lock() {
+            match manager.load_named_pack(name, priority, json) {
+                Ok(()) => 0,
+                Err(e) => {
+                    write_error(out_err, &format!("{}", e));
+                    -1
+                }
+            }
+        } else {
+            write_error(out_err, "Failed to acquire alias manager lock");
+            -1
+        }
+    }
+
+    /// Unload the pack called `name`: 0 when removed, 1 when not loaded
+    #[no_mangle]
+    pub extern "C" fn yc_remove_alias_pack(
+        name_utf8: *const c_char,
+        len: usize,
+        out_err: *mut yc_error,
+    ) -> c_int {
+        let name = match read_str(name_utf8, len) {
+            Ok(name) => name,
+            Err(e) => {
+                write_error(out_err, &e);
+                return -1;
+            }
+        };
+
+        if let Ok(mut manager) = get_alias_manager().lock() {
+            match manager.remove_pack(name) {
+                Some(_) => 0,
+                None => 1,
+            }
+        } else {
+            write_error(out_err, "Failed to acquire alias manager lock");
+            -1
+        }
+    }
+
+    /// Write the loaded packs to `out_buf` as a JSON array, lowest priority
+    /// first. Release the buffer with `yc_buffer_free`.
+    #[no_mangle]
+    pub extern "C" fn yc_list_alias_packs(
+        out_buf: *mut yc_buffer,
+        out_err: *mut yc_error,
+    ) -> c_int {
+        if let Ok(manager) = get_alias_manager().lock() {
+            write_json(&manager.packs(), out_buf, out_err)
+        } else {
+            write_error(out_err, "Failed to acquire alias manager lock");
+            -1
+        }
+    }
+
+    /// Write every locale the loaded packs cover to `out_buf` as a JSON
+    /// array. Release the buffer with `yc_buffer_free`.
+    #[no_mangle]
+    pub extern "C" fn yc_list_alias_locales(
+        out_buf: *mut yc_buffer,
+        out_err: *mut yc_error,
+    ) -> c_int {
+        if let Ok(manager) = get_alias_manager().lock() {
+            write_json(&manager.locales(), out_buf, out_err)
+        } else {
+            write_error(out_err, "Failed to acquire alias manager lock");
+            -1
+        }
+    }
+
+    /// Write which pack and locale resolve `word` to `out_buf` as JSON.
+    /// `kind` is 0 for keys, 1 for types and 2 for countries; a null
+    /// `locale_utf8` means the default locale. Returns 1 when nothing
+    /// matches, leaving `out_buf` untouched.
+    #[no_mangle]
+    pub extern "C" fn yc_explain_alias(
+        kind: c_int,
+        word_utf8: *const c_char,
+        word_len: usize,
+        locale_utf8: *const c_char,
+        locale_len: usize,
+        out_buf: *mut yc_buffer,
+        out_err: *mut yc_error,
+    ) -> c_int {
+        let kind = match kind {
+            0 => crate::i18n::AliasKind::Key,
+            1 => crate::i18n::AliasKind::Type,
+            2 => crate::i18n::AliasKind::Country,
+            _ => {
+                write_error(out_err, "Invalid alias kind");
+                return -1;
+            }
+        };
+        let word = match read_str(word_utf8, word_len) {
+            Ok(word) => word,
+            Err(e) => {
+                write_error(out_err, &e);
+                return -1;
+            }
+        };
+        let locale = if locale_utf8.is_null() {
+            None
+        } else {
+            match read_str(locale_utf8, locale_len) {
+                Ok(locale) => Some(locale),
+                Err(e) => {
+                    write_error(out_err, &e);
+                    return -1;
+                }
+            }
+        };
+
+        if let Ok(manager) = get_alias_manager().lock() {
+            match manager.explain(kind, word, locale) {
+                Some(resolution) => write_json(&resolution, out_buf, out_err),
+                None => 1,
+            }
+        } else {
+            write_error(out_err, "Failed to acquire alias manager lock");
+            -1
+        }
+    }
+
+    /// Release a buffer filled by this library
+    #[no_mangle]
+    pub extern "C" fn yc_buffer_free(buf: *mut yc_buffer) {
+        if buf.is_null() {
+            return;
+        }
+        unsafe {
+            let buf = &mut *buf;
+            if !buf.data.is_null() {
+                drop(Vec::from_raw_parts(buf.data, buf.len, buf.capacity));
+            }
+            buf.data = std::ptr::null_mut();
+            buf.len = 0;
+            buf.capacity = 0;
+        }
+    }
 }
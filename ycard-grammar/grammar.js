@@ -7,7 +7,7 @@ const PREC = {
 };
 
 // Import generated keys from schema
-const { PHONE_SHORTHAND_KEYS, EMAIL_SHORTHAND_KEYS } = require('./generated_keys');
+const { PHONE_SHORTHAND_KEYS, EMAIL_SHORTHAND_KEYS, ADDRESS_SHORTHAND_KEYS } = require('./generated_keys');
 
 module.exports = grammar({
   name: 'ycard',
@@ -34,6 +34,7 @@ module.exports = grammar({
         $.plain_scalar,
         $.phone_shorthand_key,
         $.email_shorthand_key,
+        $.address_shorthand_key,
         $.quoted_scalar,
       )),
       ':',
@@ -44,6 +45,8 @@ module.exports = grammar({
 
     email_shorthand_key: $ => choice(...EMAIL_SHORTHAND_KEYS),
 
+    address_shorthand_key: $ => choice(...ADDRESS_SHORTHAND_KEYS),
+
     _block_value: $ => choice(
       $.plain_scalar,
       $.quoted_scalar,
@@ -12,6 +12,8 @@
 
 (email_shorthand_key) @property.builtin
 
+(address_shorthand_key) @property.builtin
+
 ; Values
 (plain_scalar) @string
 (double_quoted_scalar) @string
@@ -220,7 +220,7 @@ async function validateTextDocument(textDocument: TextDocument): Promise<void> {
       ? ycard.ValidationMode.Lenient 
       : ycard.ValidationMode.Strict;
     
-    const ycardDiagnostics = await ycard.validate(parsed, validationMode);
+    const ycardDiagnostics = await ycard.validate(parsed, validationMode, settings.locale);
     
     // Convert yCard diagnostics to LSP diagnostics
     for (const ycardDiag of ycardDiagnostics) {
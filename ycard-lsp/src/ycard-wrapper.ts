@@ -14,14 +14,14 @@ export function loadAliasPack(content: string): Promise<void> {
     return Promise.resolve(wasm.load_alias_pack(content));
 }
 
-export function validate(ycard: any, mode?: any): Promise<any[]> {
+export function validate(ycard: any, mode?: any, locale?: string): Promise<any[]> {
     // Convert yCard object to JSON string
     const jsonStr = JSON.stringify(ycard);
     
     // Determine validation mode
     const modeStr = (mode === ValidationMode.Strict) ? 'strict' : 'lenient';
     
-    return Promise.resolve(wasm.validate_ycard(jsonStr, modeStr));
+    return Promise.resolve(wasm.validate_ycard(jsonStr, modeStr, locale));
 }
 
 export function format(ycard: any, phonesStyle?: any): Promise<string> {
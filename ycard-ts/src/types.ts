@@ -83,6 +83,8 @@ export interface Diagnostic {
   code?: string;
   range?: Range;
   fixes: CodeFix[];
+  /** JSON pointer to the offending value */
+  path?: string;
 }
 
 export enum DiagnosticLevel {
@@ -105,7 +107,12 @@ export interface Position {
 export interface CodeFix {
   title: string;
   kind: string;
-  edit: TextEdit;
+  /** The edit to the source; absent for a fix on the canonical card */
+  edit?: TextEdit;
+  /** JSON pointer to the value the fix replaces */
+  path?: string;
+  /** The replacement value at `path` */
+  value?: string;
 }
 
 export interface TextEdit {